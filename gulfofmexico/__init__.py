@@ -13,6 +13,16 @@ Execution Flow:
     6. Handle exports between file sections
     7. Wait for async/when statements to complete
 
+check_syntax() runs only steps 1-3, for linting without side effects.
+run_source() runs steps 1-6 on a string, for embedding the interpreter in other
+Python programs; unlike run_file() it returns as soon as the code has run. Its
+variables argument seeds each section with values from the host program, and
+its stdout and stderr arguments send the output somewhere else.
+run_capturing() wraps run_source() and hands back everything that was printed.
+run_sections() runs steps 2-6 and reports the time spent in each phase. With
+stream=True, steps 3 and 5 are interleaved: each statement is parsed right before
+it runs, so the whole syntax tree of a large file is never held at once.
+
 Multi-File Support:
     Files can be split into sections using ===== markers:
         ===== section_name =====
@@ -24,42 +34,52 @@ Global Variables:
     - Public globals from GitHub repository (if available)
 """
 
+import io
 import re
 import sys
-from time import sleep
-from typing import Optional, Union
+from contextlib import redirect_stderr, redirect_stdout
+from dataclasses import dataclass
+from time import perf_counter, sleep
+from typing import Optional, TextIO, Union
 
+from gulfofmexico.base import InterpretationError
 from gulfofmexico.builtin import KEYWORDS, Name, GulfOfMexicoValue, Variable
 from gulfofmexico.processor.lexer import tokenize
-from gulfofmexico.processor.syntax_tree import generate_syntax_tree
+from gulfofmexico.processor.syntax_tree import (
+    generate_syntax_tree,
+    iter_syntax_tree,
+    parse_next_statement,
+)
 from gulfofmexico.interpreter import (
     interpret_code_statements_main_wrapper,
     load_global_gulfofmexico_variables,
     load_globals,
     load_public_global_variables,
+    run_due_after_bodies,
 )
 
-__all__ = ["run_file"]
+__all__ = [
+    "run_file",
+    "run_source",
+    "run_capturing",
+    "CapturedRun",
+    "run_sections",
+    "check_syntax",
+    "PhaseTimings",
+    "GulfOfMexicoValue",
+    "tokenize",
+    "generate_syntax_tree",
+    "iter_syntax_tree",
+    "parse_next_statement",
+]
 
 __REPL_FILENAME = "__repl__"
 sys.setrecursionlimit(100000)
 
 
-def run_file(main_filename: str) -> None:
-    """Execute a Gulf of Mexico source file.
-
-    Reads the file, splits by ===== markers, tokenizes, parses, and executes
-    each section. Handles export/import between sections. Waits for async
-    operations and when-statements after completion.
-
-    Args:
-        main_filename: Path to .gom source file
-    """
-
-    with open(main_filename, "r", encoding="utf-8") as f:
-        code_lines = f.readlines()
-
-    # split up into separate 'files' by finding which lines start with multiple equal signs
+def split_file_sections(code_lines: list[str]) -> list[tuple[Optional[str], str]]:
+    """Split source lines into (section name, code) pairs at ===== markers."""
+    code_lines = list(code_lines)
     files: list[tuple[Optional[str], str]] = []
     if any(matches := [re.match(r"=====.*", l) for l in code_lines]):
         for i, match in reversed([*enumerate(matches)]):
@@ -71,18 +91,88 @@ def run_file(main_filename: str) -> None:
         files.insert(0, (None, "".join(code_lines[0:])))
     else:
         files = [(None, "".join(code_lines))]
+    return files
+
+
+def check_syntax(code: str) -> Optional[str]:
+    """Tokenize and parse every section of the code without running anything.
+
+    Returns the error message of the first section that fails, or None if the
+    code parses. Nothing is executed, so no globals are loaded or written.
+    """
+    for filename, section_code in split_file_sections(code.splitlines(keepends=True)):
+        filename = filename or "__unnamed_file__"
+        try:
+            tokens = tokenize(filename, section_code)
+            generate_syntax_tree(filename, tokens, section_code)
+        except InterpretationError as e:
+            return str(e)
+    return None
+
+
+@dataclass
+class PhaseTimings:
+    """Wall-clock seconds spent in each phase, summed over all file sections."""
+
+    tokenize: float = 0.0
+    parse: float = 0.0
+    execute: float = 0.0
+
+    def summary(self) -> str:
+        return (
+            f"tokenize: {self.tokenize:.4f}s, parse: {self.parse:.4f}s, "
+            f"execute: {self.execute:.4f}s"
+        )
+
+
+def run_sections(
+    files: list[tuple[Optional[str], str]], stream: bool = False
+) -> PhaseTimings:
+    """Tokenize, parse and execute each file section in order.
+
+    Exports from one section are made importable by the sections after it.
+    Returns how long each phase took. When streaming, statements are parsed
+    as they are executed, so parsing counts towards the execute phase.
+    """
+    timings = PhaseTimings()
+    _execute_sections(files, stream, timings)
+    return timings
+
+
+def seed_globals(
+    namespaces: list[dict[str, Union[Variable, Name]]],
+    variables: dict[str, GulfOfMexicoValue],
+) -> None:
+    """Bind host-provided values in the outermost namespace, next to the keywords."""
+    for name, value in variables.items():
+        namespaces[0][name] = Name(name, value)
 
-    # execute code for each file
+
+def _execute_sections(
+    files: list[tuple[Optional[str], str]],
+    stream: bool,
+    timings: PhaseTimings,
+    variables: Optional[dict[str, GulfOfMexicoValue]] = None,
+) -> Optional[GulfOfMexicoValue]:
+    """Shared by run_sections and run_source. Returns the last section's result."""
+    import gulfofmexico.interpreter as interpreter
+
+    result = None
     importable_names: dict[str, dict[str, GulfOfMexicoValue]] = {}
     for filename, code in files:
         filename = filename or "__unnamed_file__"
         # Set global variables for interpreter
-        import gulfofmexico.interpreter as interpreter
-
         interpreter.filename = filename
         interpreter.code = code
+        start = perf_counter()
         tokens = tokenize(filename, code)
-        statements = generate_syntax_tree(filename, tokens, code)
+        timings.tokenize += perf_counter() - start
+        start = perf_counter()
+        if stream:
+            statements = iter_syntax_tree(filename, tokens, code)
+        else:
+            statements = generate_syntax_tree(filename, tokens, code)
+        timings.parse += perf_counter() - start
 
         # load variables and run the code
         # Use Name objects directly for keywords
@@ -98,15 +188,97 @@ def run_file(main_filename: str) -> None:
         )
         load_global_gulfofmexico_variables(namespaces)
         load_public_global_variables(namespaces)
-        interpret_code_statements_main_wrapper(
+        seed_globals(namespaces, variables or {})
+        start = perf_counter()
+        result = interpret_code_statements_main_wrapper(
             statements, namespaces, [], [{}], importable_names, exported_names
         )
+        timings.execute += perf_counter() - start
 
         # take exported names and put them where they belong
         for target_filename, name, value in exported_names:
             if target_filename not in importable_names:
                 importable_names[target_filename] = {}
             importable_names[target_filename][name] = value
+    return result
+
+
+def run_source(
+    code: str,
+    filename: Optional[str] = None,
+    stream: bool = False,
+    variables: Optional[dict[str, GulfOfMexicoValue]] = None,
+    stdout: Optional[TextIO] = None,
+    stderr: Optional[TextIO] = None,
+) -> Optional[GulfOfMexicoValue]:
+    """Execute Gulf of Mexico source code held in a string.
+
+    The code may contain ===== file markers like a source file. filename names
+    the first section in error messages. variables maps names to values every
+    section can read, like builtins the code may shadow. stdout and stderr
+    replace the process streams while the code runs (output from a later
+    when-statement or after-statement still goes to the process streams).
+    Returns the value of the last statement once every statement has run,
+    leaving any when-statements and after-statements registered.
+    """
+    files = split_file_sections(code.splitlines(keepends=True))
+    if filename is not None and files[0][0] is None:
+        files[0] = (filename, files[0][1])
+    with redirect_stdout(stdout or sys.stdout), redirect_stderr(stderr or sys.stderr):
+        return _execute_sections(files, stream, PhaseTimings(), variables)
+
+
+@dataclass
+class CapturedRun:
+    """What run_capturing() saw: both output streams, and the result or the error."""
+
+    stdout: str
+    stderr: str
+    value: Optional[GulfOfMexicoValue] = None
+    error: Optional[InterpretationError] = None
+
+
+def run_capturing(
+    code: str,
+    filename: Optional[str] = None,
+    variables: Optional[dict[str, GulfOfMexicoValue]] = None,
+) -> CapturedRun:
+    """Run code like run_source(), collecting its output instead of printing it.
+
+    An InterpretationError is returned in the result rather than raised, so the
+    output printed before the error is not lost.
+    """
+    stdout, stderr = io.StringIO(), io.StringIO()
+    try:
+        value = run_source(
+            code, filename, variables=variables, stdout=stdout, stderr=stderr
+        )
+    except InterpretationError as e:
+        return CapturedRun(stdout.getvalue(), stderr.getvalue(), error=e)
+    return CapturedRun(stdout.getvalue(), stderr.getvalue(), value)
+
+
+def run_file(
+    main_filename: str, show_timing: bool = False, stream: bool = False
+) -> None:
+    """Execute a Gulf of Mexico source file.
+
+    Reads the file, splits by ===== markers, tokenizes, parses, and executes
+    each section. Handles export/import between sections. Waits for async
+    operations and when-statements after completion.
+
+    Args:
+        main_filename: Path to .gom source file
+        show_timing: Print how long tokenizing, parsing and executing took
+        stream: Parse each statement just before running it (see run_sections)
+    """
+
+    with open(main_filename, "r", encoding="utf-8") as f:
+        code_lines = f.readlines()
+
+    timings = run_sections(split_file_sections(code_lines), stream=stream)
+    if show_timing:
+        print(timings.summary(), file=sys.stderr, flush=True)
 
     print(
         "\033[33mCode has finished executing. Press ^C once or twice to stop waiting for when-statements and after-statements.\033[039m",
@@ -114,6 +286,7 @@ def run_file(main_filename: str) -> None:
     )
     try:
         while True:
-            sleep(1)  # just waiting for any clicks, when statements, etc
+            sleep(0.05)  # just waiting for any clicks, when statements, etc
+            run_due_after_bodies()
     except KeyboardInterrupt:
         exit()  # quit silently
@@ -13,15 +13,42 @@ Usage Modes:
     3. Inline code:
        $ python -m gulfofmexico -c "const x 123! print(x)!"
 
-    4. Debug mode (show Python traceback):
+    4. Debug mode (show Python traceback and the Gulf of Mexico call stack):
        $ python -m gulfofmexico -s script.gom
 
+    5. Bounded execution (abort after N statements):
+       $ python -m gulfofmexico --max-statements 10000 script.gom
+
+    6. Syntax check only (nothing is executed):
+       $ python -m gulfofmexico --check script.gom
+
+    7. Script arguments (everything after the file, read with args()):
+       $ python -m gulfofmexico script.gom one two --three
+
+    8. Phase timing (tokenize / parse / execute, printed to stderr):
+       $ python -m gulfofmexico --time script.gom
+
+    9. Streaming (parse each statement right before running it):
+       $ python -m gulfofmexico --stream big_generated.gom
+
+    10. Lint warnings (declarations shadowing builtins or outer consts):
+       $ python -m gulfofmexico --lint script.gom
+
+    11. Inline assertions (a statement ending in ? must evaluate to true):
+       $ python -m gulfofmexico --assert script.gom
+
+    12. Conventional indexing (lists and strings start at 0, -1 is the last item):
+       $ python -m gulfofmexico --zero-based script.gom
+
+    13. Number display (plain, thousands or scientific):
+       $ python -m gulfofmexico --numbers thousands script.gom
+
 All modes use the production interpreter in gulfofmexico/interpreter.py.
 The experimental gulfofmexico/engine/ is never used.
 
 Execution Path:
     - File mode: run_file() from gulfofmexico/__init__.py
-    - Inline mode: _run_inline() direct interpreter invocation
+    - Inline mode: run_source() from gulfofmexico/__init__.py
     - REPL mode: repl_main() from gulfofmexico/repl.py
 """
 
@@ -31,7 +58,8 @@ import argparse
 import sys
 from typing import Optional
 
-from gulfofmexico import run_file
+from gulfofmexico import check_syntax, run_file, run_source
+from gulfofmexico.builtin import NUMBER_DISPLAY_MODES
 from gulfofmexico.repl import main as repl_main
 
 
@@ -45,43 +73,8 @@ def _run_inline(code: str, show_tb: bool) -> int:
     Returns:
         Exit code (0 for success, 1 for error)
     """
-    import gulfofmexico.interpreter as interpreter
-    from gulfofmexico.processor.lexer import tokenize
-    from gulfofmexico.processor.syntax_tree import generate_syntax_tree
-    from gulfofmexico.builtin import KEYWORDS, Name, GulfOfMexicoValue, Variable
-    from typing import Union
-
     try:
-        filename = "__inline__"
-        interpreter.filename = filename
-        interpreter.code = code
-
-        tokens = tokenize(filename, code)
-        statements = generate_syntax_tree(filename, tokens, code)
-
-        namespaces: list[dict[str, Union[Variable, Name]]] = [KEYWORDS.copy()]  # type: ignore
-        exported_names: list[tuple[str, str, GulfOfMexicoValue]] = []
-        importable_names: dict[str, dict[str, GulfOfMexicoValue]] = {}
-
-        interpreter.load_globals(
-            filename,
-            code,
-            {},
-            set(),
-            exported_names,
-            importable_names.get(filename, {}),
-        )
-        interpreter.load_global_gulfofmexico_variables(namespaces)
-        interpreter.load_public_global_variables(namespaces)
-
-        interpreter.interpret_code_statements_main_wrapper(
-            statements,
-            namespaces,
-            [],
-            [{}],
-            importable_names,
-            exported_names,
-        )
+        run_source(code, filename="__inline__")
         return 0
     except Exception:
         if show_tb:
@@ -95,15 +88,123 @@ def _main(argv: Optional[list[str]] = None) -> int:
 
     parser = argparse.ArgumentParser(prog="gulfofmexico", add_help=True)
     parser.add_argument("file", nargs="?", help="Gulf of Mexico source file (.gom)")
+    parser.add_argument(
+        "script_args",
+        nargs=argparse.REMAINDER,
+        help="arguments passed to the script, available through args()",
+    )
     parser.add_argument(
         "-s",
         "--show-traceback",
         action="store_true",
-        help="show full Python traceback on errors",
+        help="show full Python traceback and the active function calls on errors",
     )
     parser.add_argument("-c", dest="inline_code", help="run inline code and exit")
+    parser.add_argument(
+        "--max-statements",
+        type=int,
+        default=None,
+        metavar="N",
+        help="abort with an error after executing N statements",
+    )
+    parser.add_argument(
+        "--lenient",
+        action="store_true",
+        help="treat unknown characters as part of names instead of erroring",
+    )
+    parser.add_argument(
+        "--defer-when",
+        action="store_true",
+        help="only run when blocks after a watched variable changes",
+    )
+    parser.add_argument(
+        "--no-globals",
+        action="store_true",
+        help="start with a clean slate instead of loading persisted global variables",
+    )
+    parser.add_argument(
+        "--check",
+        action="store_true",
+        help="only check the file (or -c code) for syntax errors, without running it",
+    )
+    parser.add_argument(
+        "--time",
+        action="store_true",
+        help="print how long tokenizing, parsing and executing the file took",
+    )
+    parser.add_argument(
+        "--stream",
+        action="store_true",
+        help="parse each statement just before running it instead of all up front",
+    )
+    parser.add_argument(
+        "--no-color",
+        action="store_true",
+        help="print REPL results without colors",
+    )
+    parser.add_argument(
+        "--lint",
+        action="store_true",
+        help="warn when a declaration shadows a builtin or an outer const",
+    )
+    parser.add_argument(
+        "--assert",
+        dest="assert_on_debug",
+        action="store_true",
+        help="make a statement ending in ? fail unless its value is true",
+    )
+    parser.add_argument(
+        "--zero-based",
+        action="store_true",
+        help="index lists and strings from 0; negative indices count from the end",
+    )
+    parser.add_argument(
+        "--numbers",
+        choices=NUMBER_DISPLAY_MODES,
+        default="plain",
+        help="how numbers are shown: plain (1000000), thousands (1,000,000) "
+        "or scientific (1e+06)",
+    )
+    parser.add_argument(
+        "--all-errors",
+        action="store_true",
+        help="report every statement that fails to parse, not just the first",
+    )
     ns = parser.parse_args(args)
 
+    import gulfofmexico.builtin as builtin
+    import gulfofmexico.interpreter as interpreter
+    import gulfofmexico.processor.lexer as lexer
+    import gulfofmexico.processor.syntax_tree as syntax_tree
+
+    interpreter.max_statements = ns.max_statements
+    interpreter.show_call_stack = ns.show_traceback
+    interpreter.warn_on_shadowing = ns.lint
+    interpreter.assert_on_debug = ns.assert_on_debug
+    interpreter.zero_based_indexing = ns.zero_based
+    builtin.number_display = ns.numbers
+    lexer.allow_unknown_characters = ns.lenient
+    interpreter.defer_when_statements = ns.defer_when
+    interpreter.load_persisted_globals = not ns.no_globals
+    interpreter.script_args = ns.script_args
+    syntax_tree.collect_parse_errors = ns.all_errors
+
+    # Syntax check mode
+    if ns.check:
+        if ns.inline_code is not None:
+            source = ns.inline_code
+        elif ns.file:
+            with open(ns.file) as f:
+                source = f.read()
+        else:
+            parser.error("--check needs a file or -c code")
+        error = check_syntax(source)
+        if error is not None:
+            print(error, file=sys.stderr)
+            return 1
+        print("OK")
+        return 0
+
     # Inline code mode
     if ns.inline_code is not None:
         try:
@@ -116,7 +217,7 @@ def _main(argv: Optional[list[str]] = None) -> int:
     # File mode
     if ns.file:
         try:
-            run_file(ns.file)
+            run_file(ns.file, show_timing=ns.time, stream=ns.stream)
             return 0
         except Exception:
             if ns.show_traceback:
@@ -125,7 +226,7 @@ def _main(argv: Optional[list[str]] = None) -> int:
 
     # Default: REPL
     try:
-        return repl_main([])
+        return repl_main(["--no-color"] if ns.no_color else [])
     except Exception:
         if ns.show_traceback:
             raise
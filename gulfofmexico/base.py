@@ -14,6 +14,7 @@ Error Handling:
     - raise_error_at_token(): Display error with source location
     - raise_error_at_line(): Display error at line number
     - debug_print(): Print debug messages with source context
+    - normalize_newlines(): Turn CRLF and CR line endings into LF
 
 Constants:
     - ALPH_NUMS: Valid characters in names (includes dots for namespaces)
@@ -27,11 +28,14 @@ Color Codes:
 
 from __future__ import annotations
 
+import re
 from enum import Enum
 from typing import NoReturn, Optional
 from dataclasses import dataclass, field
 
 ALPH_NUMS = set("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_.")
+# one escape sequence in the text of a string literal, without its backslash
+ESCAPE_PATTERN = re.compile(r"\\(u\{[^}]*\}|u[0-9a-fA-F]{4}|.)", re.DOTALL)
 
 
 class NonFormattedError(Exception):
@@ -41,10 +45,70 @@ class NonFormattedError(Exception):
 
 
 class InterpretationError(Exception):
-    """Gulf of Mexico runtime error with formatted message."""
+    """Gulf of Mexico runtime error with formatted message.
+
+    str() gives the formatted message. The bare message and the position it points
+    at are kept too, and the subclasses below say what kind of error it was.
+    """
 
     _: str  # Intentionally obfuscated for style
 
+    def __init__(
+        self,
+        formatted: str = "",
+        message: Optional[str] = None,
+        line: Optional[int] = None,
+        col: Optional[int] = None,
+    ) -> None:
+        super().__init__(formatted)
+        self.message = formatted if message is None else message
+        self.line = line
+        self.col = col
+        # user function calls active when it was raised, outermost first: (name, line)
+        self.call_stack: list[tuple[str, int]] = []
+
+
+class ParseError(InterpretationError):
+    """The code could not be tokenized or parsed."""
+
+
+class UndefinedNameError(InterpretationError):
+    """A name was used that no enclosing scope declares."""
+
+    def __init__(self, *args, name: str = "", **kwargs) -> None:
+        super().__init__(*args, **kwargs)
+        self.name = name
+
+
+class TypeMismatchError(InterpretationError):
+    """A value was not of the type the code needed."""
+
+    def __init__(self, *args, expected: str = "", got: str = "", **kwargs) -> None:
+        super().__init__(*args, **kwargs)
+        self.expected = expected
+        self.got = got
+
+
+def normalize_newlines(code: str) -> str:
+    """Turn CRLF (Windows) and lone CR line endings into LF."""
+    return code.replace("\r\n", "\n").replace("\r", "\n")
+
+
+# columns per tab when a source line is shown in an error or debug message
+TAB_WIDTH: int = 4
+
+
+def source_line(code: str, line: int) -> str:
+    return normalize_newlines(code).split("\n")[line - 1].expandtabs(TAB_WIDTH)
+
+
+def caret_line(code: str, token: Token) -> str:
+    """Carets under token, lined up with its source_line() (tabs expanded)."""
+    start = token.col - len(token.value) + 1
+    raw_line = normalize_newlines(code).split("\n")[token.line - 1]
+    num_spaces = len(raw_line[:start].expandtabs(TAB_WIDTH)) + 1
+    return f" {num_spaces * ' '}{len(token.value) * '^'}"
+
 
 def debug_print(filename: str, code: str, message: str, token: Token) -> None:
     """Print debug message with source code context.
@@ -56,11 +120,10 @@ def debug_print(filename: str, code: str, message: str, token: Token) -> None:
         print(f"\n\033[33m{message}\033[39\n", sep="")
         return
     line = token.line
-    num_carrots, num_spaces = len(token.value), token.col - len(token.value) + 1
     debug_string = (
         f"\033[33m{filename}, line {line}\033[39m\n\n"
-        + f"  {code.split(chr(10))[line - 1]}\n"
-        + f" {num_spaces * ' '}{num_carrots * '^'}\n"
+        + f"  {source_line(code, line)}\n"
+        + f"{caret_line(code, token)}\n"
         + f"\033[33m{message}\033[39m"
     )
     print("\n", debug_string, "\n", sep="")
@@ -72,30 +135,46 @@ def debug_print_no_token(filename: str, message: str) -> None:
 
 
 def raise_error_at_token(
-    filename: str, code: str, message: str, token: Token
+    filename: str,
+    code: str,
+    message: str,
+    token: Token,
+    error_type: type[InterpretationError] = InterpretationError,
+    **details,
 ) -> NoReturn:
-    if not code:  # adjust for repl-called code
-        raise InterpretationError(f"\n\033[31m{message}\033[39m\n")
+    """Raise error_type pointing at token. details go to the error's constructor."""
     line = token.line
-    num_carrots, num_spaces = len(token.value), token.col - len(token.value) + 1
-    error_string = (
-        f"\033[33m{filename}, line {line}\033[39m\n\n"
-        + f"  {code.split(chr(10))[line - 1]}\n"
-        + f" {num_spaces * ' '}{num_carrots * '^'}\n"
-        + f"\033[31m{message}\033[39m"
-    )
-    raise InterpretationError(error_string)
-
-
-def raise_error_at_line(filename: str, code: str, line: int, message: str) -> NoReturn:
+    num_spaces = token.col - len(token.value) + 2  # 1-based column of the token
     if not code:  # adjust for repl-called code
-        raise InterpretationError(f"\n\033[31m{message}\033[39m\n")
-    error_string = (
-        f"\033[33m{filename}, line {line}\033[39m\n\n"
-        + f"  {code.split(chr(10))[line - 1]}\n\n"
-        + f"\033[31m{message}\033[39m"
-    )
-    raise InterpretationError(error_string)
+        error_string = f"\n\033[31m{message}\033[39m\n"
+    else:
+        error_string = (
+            f"\033[33m{filename}, line {line}\033[39m\n\n"
+            + f"  {source_line(code, line)}\n"
+            + f"{caret_line(code, token)}\n"
+            + f"\033[31m{message}\033[39m"
+        )
+    raise error_type(error_string, message, line, num_spaces, **details)
+
+
+def raise_error_at_line(
+    filename: str,
+    code: str,
+    line: int,
+    message: str,
+    error_type: type[InterpretationError] = InterpretationError,
+    **details,
+) -> NoReturn:
+    """Raise error_type pointing at a line. details go to the error's constructor."""
+    if not code:  # adjust for repl-called code
+        error_string = f"\n\033[31m{message}\033[39m\n"
+    else:
+        error_string = (
+            f"\033[33m{filename}, line {line}\033[39m\n\n"
+            + f"  {source_line(code, line)}\n\n"
+            + f"\033[31m{message}\033[39m"
+        )
+    raise error_type(error_string, message, line, **details)
 
 
 class TokenType(Enum):
@@ -117,9 +196,12 @@ class TokenType(Enum):
     COLON = ":"
     SEMICOLON = ";"
     BANG = "!"
+    CONFIDENCE = "(!)"  # a ! inside parentheses, e.g. (5!) + 3
     QUESTION = "?"
     CARROT = "^"
     FUNC_POINT = "=>"
+    ASSIGN_ARROW = "<-"  # const const x <- 5! is the same as const const x = 5!
+    COMPOUND_ASSIGN = "+="  # also -=, *= and /=, the value says which
 
     LESS_THAN = "<"
     GREATER_THAN = ">"
@@ -162,6 +244,7 @@ class OperatorType(Enum):
     NE = ";="
     NEE = ";=="
     NEEE = ";==="
+    IN = "in"
 
 
 STR_TO_OPERATOR = {op.value: op for op in OperatorType}
@@ -175,7 +258,7 @@ class Token:
     type: TokenType
     value: str
     line: int = field(hash=False)
-    col: int = field(hash=False)
+    col: int = field(hash=False)  # 0-based column of the last character
 
     def __repr__(self) -> str:
         return f"Token({self.type}, {repr(self.value)})"
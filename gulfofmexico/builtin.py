@@ -29,24 +29,41 @@ Key Features:
 
 Built-in Functions:
     - Type conversions: Number(), String(), Boolean()
+    - Sizes: len() for strings, lists, and maps
+    - Sequences: range(end), range(start, end), range(start, end, step)
+    - Pairing: enumerate() (indices start at -1), zip()
     - I/O: print(), read(), write()
     - Control: sleep(), exit()
+    - Fallbacks: coalesce() swaps undefined or maybe for a default
+    - Introspection: line_count(), args(), lifetime() for a variable's time left
+    - Overriding: restore_builtin() brings back a builtin after it was redeclared
+    - Discovery: builtins() lists every builtin function with its argument count
+    - Reactivity: last_when(), stop_when() to deregister when statements
+    - Metaprogramming: eval() runs a string of code in the caller's scope,
+      import_value() runs a file on its own and returns its last value
+    - Environment: getenv(), setenv()
     - Data structures: Map(), use() for signals
     - Math functions: All from Python's math module
+    - Strings: starts_with(), ends_with(), trim(), replace()
+    - Characters: char_at(), char_code(), from_char_code()
     - Regex: regex_match(), regex_findall(), regex_replace()
+    - Templating: format() with {0}, {1} placeholders
     - Word numbers: one, two, twenty, thirty, etc.
 """
 
 from __future__ import annotations
 import functools
+import os
+import sys
 import time
 from time import sleep
 
 import math
 from abc import ABCMeta, abstractmethod
 from dataclasses import dataclass, field
-from typing import Callable, Optional, Union
-from gulfofmexico.base import NonFormattedError
+from typing import Callable, Iterable, Optional, Union
+from gulfofmexico.base import NonFormattedError, Token
+from gulfofmexico.constants import INFINITE_LIFETIME
 
 from gulfofmexico.processor.syntax_tree import CodeStatement
 
@@ -67,11 +84,46 @@ __all__ = [
 
 FLOAT_TO_INT_PREC = 0.00000001
 
+# strings produced by String(true/false/maybe), so they convert back losslessly
+BOOLEAN_STRINGS: dict[str, Optional[bool]] = {"true": True, "false": False, "maybe": None}
+
+
+# floats hold every integer below this exactly
+EXACT_INT_LIMIT = 2**53
+
 
 def is_int(x: Union[float, int]) -> bool:
     return min(x % 1, 1 - x % 1) < FLOAT_TO_INT_PREC
 
 
+# how print() and the REPL show numbers (see --numbers): "plain" (1000000),
+# "thousands" (1,000,000) or "scientific" (1e+06). String() always gives plain
+NUMBER_DISPLAY_MODES = ("plain", "thousands", "scientific")
+number_display: str = "plain"
+
+
+def format_number(value: float, mode: str = "plain") -> str:
+    # integer-valued numbers print without a decimal point; round() (not int())
+    # so -2.999999996 shows as -3, and -0.0 shows as 0. Tiny values like 1e-9 are
+    # not rounded to 0, and past 2**53 floats keep their exponent form
+    if not math.isfinite(value):
+        return str(value)  # inf, -inf and nan look the same in every mode
+    nearest = round(value)
+    if abs(value) < EXACT_INT_LIMIT and (
+        value == nearest or (nearest and is_int(value))
+    ):
+        number = nearest
+    else:
+        number = value
+    match mode:
+        case "thousands":
+            return f"{number:,}"
+        case "scientific":
+            mantissa, exponent = f"{number:.6e}".split("e")
+            return f"{mantissa.rstrip('0').rstrip('.')}e{exponent}"
+    return str(number)
+
+
 def db_not(x: GulfOfMexicoBoolean) -> GulfOfMexicoBoolean:
     if x.value is None:
         return GulfOfMexicoBoolean(None)
@@ -141,7 +193,35 @@ def db_str_pop(
 
 
 class GulfOfMexicoValue:  # base class for shit
-    pass
+    """Base of every runtime value.
+
+    Programs embedding the interpreter can build and read values through the
+    helpers below instead of the wrapper classes.
+    """
+
+    @staticmethod
+    def number(value: Union[int, float]) -> GulfOfMexicoNumber:
+        return GulfOfMexicoNumber(value)
+
+    @staticmethod
+    def string(value: str) -> GulfOfMexicoString:
+        return GulfOfMexicoString(value)
+
+    @staticmethod
+    def list(values: Iterable[GulfOfMexicoValue]) -> GulfOfMexicoList:
+        return GulfOfMexicoList([*values])
+
+    def as_number(self) -> Optional[Union[int, float]]:
+        """The number held by a Number, or None for any other value."""
+        return self.value if isinstance(self, GulfOfMexicoNumber) else None
+
+    def as_string(self) -> Optional[str]:
+        """The text held by a String, or None for any other value."""
+        return self.value if isinstance(self, GulfOfMexicoString) else None
+
+    def as_bool(self) -> Optional[bool]:
+        """True or False for a Boolean; None for maybe and for any other value."""
+        return self.value if isinstance(self, GulfOfMexicoBoolean) else None
 
 
 class GulfOfMexicoMutable(GulfOfMexicoValue):  # mutable values
@@ -168,6 +248,12 @@ class GulfOfMexicoFunction(GulfOfMexicoValue):
     args: list[str]
     code: list[tuple[CodeStatement, ...]]
     is_async: bool
+    return_type: Optional[str] = None  # declared with function f(): Type
+    # scopes of the call a function was defined in, so it can still read the
+    # enclosing function's names after that call returns (None at top level)
+    closure: Optional[list[dict[str, Union[Name, Variable]]]] = field(
+        default=None, init=False, compare=False, repr=False
+    )
 
 
 @dataclass
@@ -175,6 +261,9 @@ class BuiltinFunction(GulfOfMexicoValue):
     arg_count: int
     function: Callable
     modifies_caller: bool = False
+    takes_namespaces: bool = False  # called with the caller's namespaces first
+    takes_watchers: bool = False  # then with the caller's when watchers
+    takes_token: bool = False  # then with the name token of the call
 
 
 @dataclass
@@ -222,19 +311,20 @@ class GulfOfMexicoList(
         # print("user index:" + str(user_index))
         realIndex = self.indexer.get(user_index)
         # print("real index:" + str(realIndex))
-        return self.values[round(realIndex)]
+        # indexer values are shifted down by one like user indices, -1 is values[0]
+        return self.values[round(realIndex) + 1]
 
     def assign_index(self, index: GulfOfMexicoValue, val: GulfOfMexicoValue) -> None:
         if not isinstance(index, GulfOfMexicoNumber):
             raise NonFormattedError("Cannot index a list with a non-number value.")
+        if not -1 <= index.value <= len(self.values) - 1:
+            raise NonFormattedError(
+                f"Index {db_to_string(index).value} is out of list bounds "
+                f"(-1 to {len(self.values) - 1})."
+            )
         if index.value in self.indexer:
-            if not -1 <= index.value <= len(self.values) - 1:
-                raise NonFormattedError("Indexing out of list bounds.")
-            self.values[round(index.value)] = val
-            self.indexer[round(index.value)] = round(index.value)
+            self.values[round(self.indexer[index.value]) + 1] = val
         else:  # assign in the middle of the array
-            if not -1 <= index.value <= len(self.values) - 1:
-                raise NonFormattedError("Indexing out of list bounds.")
             nearest_int_down = round(max((index.value + 2) // 1, 0))
             self.values[nearest_int_down:nearest_int_down] = [val]
             self.indexer[index.value] = (
@@ -306,19 +396,26 @@ class GulfOfMexicoString(
     GulfOfMexicoValue,
 ):
     value: str = field(hash=True)
-    indexer: dict[float, tuple] = field(
-        init=False, hash=False
-    )  # used for converting the user decimal indecies to the real indecies
+    # used for converting the user decimal indecies to the real indecies, built on
+    # first use since most strings are never indexed (see the indexer property)
     # tuple stores the real index in the first slot and any extra characters in the second
+    _indexer: Optional[dict[float, tuple]] = field(
+        default=None, init=False, hash=False, compare=False, repr=False
+    )
     namespace: dict[str, Union[Name, Variable]] = field(
         default_factory=dict, hash=False
     )
 
     def __post_init__(self):
         self.create_namespace(False)
-        self.indexer = dict()
-        for index in range(len(self.value)):
-            self.indexer[index - 1] = (index - 1, "")
+
+    @property
+    def indexer(self) -> dict[float, tuple]:
+        if self._indexer is None:
+            self._indexer = {
+                index - 1: (index - 1, "") for index in range(len(self.value))
+            }
+        return self._indexer
 
     def create_namespace(self, is_update: bool = True):
         if not is_update:
@@ -337,7 +434,7 @@ class GulfOfMexicoString(
             raise NonFormattedError("Cannot index a string with a non-number value.")
         # if not is_int(index.value):
         #    raise NonFormattedError("Expected integer for string indexing.")
-        if not -1 <= index.value <= len(self.value) - 1:
+        if not self.value or not -1 <= index.value <= len(self.value) - 1:
             raise NonFormattedError("Indexing out of string bounds.")
         elif index.value not in self.indexer:
             raise NonFormattedError(
@@ -418,7 +515,7 @@ class GulfOfMexicoObject(GulfOfMexicoNamespaceable, GulfOfMexicoValue):
 
 
 @dataclass
-class GulfOfMexicoMap(GulfOfMexicoIndexable, GulfOfMexicoValue):
+class GulfOfMexicoMap(GulfOfMexicoIndexable, GulfOfMexicoMutable, GulfOfMexicoValue):
     self_dict: dict[Union[int, float, str], GulfOfMexicoValue]
 
     def access_index(self, index: GulfOfMexicoValue) -> GulfOfMexicoValue:
@@ -456,6 +553,7 @@ class VariableLifetime:
     can_be_reset: bool
     can_edit_value: bool
     creation_time: float = field(default_factory=lambda: time.time())
+    creation_line: int = 0
     is_temporal: bool = False
     temporal_duration: float = 0.0
 
@@ -497,9 +595,12 @@ class Variable:
         can_edit_value: bool,
         is_temporal: bool = False,
         temporal_duration: float = 0.0,
+        creation_line: int = 0,
     ) -> None:
+        # lifetimes are kept highest confidence first; a new lifetime goes above
+        # any with the same confidence, so the latest of equals is the value
         for i in range(len(self.lifetimes) + 1):
-            if i == len(self.lifetimes) or self.lifetimes[i].confidence >= confidence:
+            if i == len(self.lifetimes) or self.lifetimes[i].confidence <= confidence:
                 if i == 0 and self.lifetimes:
                     self.prev_values.append(self.value)
                 self.lifetimes[i:i] = [
@@ -511,15 +612,49 @@ class Variable:
                         can_edit_value,
                         is_temporal=is_temporal,
                         temporal_duration=temporal_duration,
+                        creation_line=creation_line,
                     )
                 ]
                 break
 
-    def clear_outdated_lifetimes(self) -> None:
+    def reassign(
+        self, value: GulfOfMexicoValue, confidence: int, creation_line: int = 0
+    ) -> None:
+        """Replace the current value with one whose lifetime restarts from now.
+
+        The new lifetime has the same length as the current one (in lines or
+        seconds), so writing to a short-lived variable keeps it alive longer.
+        It keeps the higher of the two confidences, so values layered beneath
+        by redeclaration stay beneath.
+        """
+        if not self.lifetimes:
+            raise NonFormattedError("Variable is undefined.")
+        current = self.lifetimes[0]
+        self.prev_values.append(current.value)
+        self.lifetimes[0] = VariableLifetime(
+            value,
+            current.lines_left,
+            max(confidence, current.confidence),
+            current.can_be_reset,
+            current.can_edit_value,
+            creation_line=creation_line,
+            is_temporal=current.is_temporal,
+            temporal_duration=current.temporal_duration,
+        )
+
+    @property
+    def expires(self) -> bool:
+        """Whether any of the variable's lifetimes can run out."""
+        return any(
+            l.is_temporal or l.lines_left < INFINITE_LIFETIME for l in self.lifetimes
+        )
+
+    def clear_outdated_lifetimes(self, current_line: int = 0) -> None:
+        """Drop expired lifetimes, letting the value beneath show through."""
         remove_indeces = []
         current_time = time.time()
         for i, l in enumerate(self.lifetimes):
-            if l.lines_left == 0 or (
+            if current_line - l.creation_line >= l.lines_left or (
                 l.is_temporal and current_time - l.creation_time >= l.temporal_duration
             ):
                 remove_indeces.append(i)
@@ -572,6 +707,8 @@ KEYWORDS = {
         "var",
         "when",
         "if",
+        "match",
+        "repeat",
         "async",
         "return",
         "delete",
@@ -581,15 +718,48 @@ KEYWORDS = {
         "reverse",
         "export",
         "import",
+        "debug",
     ]
     + FUNCTION_KEYWORDS
 }
+# words the parser treats specially by their spelling, so they can't be made
+# keyword aliases (an alias called else would make every if ... else ambiguous)
+RESERVED_WORDS = frozenset({"else", "to", "new", "async"})
 
 
 def db_identity(val: GulfOfMexicoValue) -> GulfOfMexicoValue:
     return val
 
 
+def db_coalesce(
+    val: GulfOfMexicoValue, fallback: GulfOfMexicoValue
+) -> GulfOfMexicoValue:
+    """The value itself, or the fallback if it is undefined or maybe."""
+    if isinstance(val, GulfOfMexicoUndefined) or (
+        isinstance(val, GulfOfMexicoBoolean) and val.value is None
+    ):
+        return fallback
+    return val
+
+
+def db_xor(a: GulfOfMexicoValue, b: GulfOfMexicoValue) -> GulfOfMexicoBoolean:
+    """Whether exactly one side is true, or maybe if either side is maybe."""
+    left, right = db_to_boolean(a).value, db_to_boolean(b).value
+    if left is None or right is None:
+        return GulfOfMexicoBoolean(None)
+    return GulfOfMexicoBoolean(left != right)
+
+
+def db_nand(a: GulfOfMexicoValue, b: GulfOfMexicoValue) -> GulfOfMexicoBoolean:
+    """not (a and b): true if either side is false, otherwise maybe if one is maybe."""
+    left, right = db_to_boolean(a).value, db_to_boolean(b).value
+    if left is False or right is False:
+        return GulfOfMexicoBoolean(True)
+    if left is None or right is None:
+        return GulfOfMexicoBoolean(None)
+    return GulfOfMexicoBoolean(False)
+
+
 def db_new(val: GulfOfMexicoValue) -> GulfOfMexicoValue:
     """Instantiate a new object from a class object.
 
@@ -630,9 +800,54 @@ def db_map() -> GulfOfMexicoMap:
     return GulfOfMexicoMap({})
 
 
+def map_key(name: str, m: GulfOfMexicoValue, key: GulfOfMexicoValue):
+    """The dict key for key in map m, erroring on a non-map or an unusable key."""
+    if not isinstance(m, GulfOfMexicoMap):
+        raise NonFormattedError(f"'{name}' requires a map.")
+    if not isinstance(key, (GulfOfMexicoString, GulfOfMexicoNumber)):
+        raise NonFormattedError("Keys of a map must be an index or a number.")
+    return key.value
+
+
+def db_map_set(
+    when_statement_watchers: list[dict],
+    m: GulfOfMexicoValue,
+    key: GulfOfMexicoValue,
+    val: GulfOfMexicoValue,
+) -> GulfOfMexicoValue:
+    """Set key in the map in place and return the map."""
+    import gulfofmexico.interpreter as interpreter
+
+    dict_key = map_key("map_set", m, key)
+    m.self_dict[dict_key] = val
+    # the same whens an index assignment like m[key] = val would check
+    interpreter.notify_value_watchers(m, [], when_statement_watchers)
+    return m
+
+
+def db_map_get(m: GulfOfMexicoValue, key: GulfOfMexicoValue) -> GulfOfMexicoValue:
+    """The value stored under key, or undefined when the map does not have it."""
+    dict_key = map_key("map_get", m, key)
+    return m.self_dict.get(dict_key, GulfOfMexicoUndefined())
+
+
+def db_map_delete(
+    when_statement_watchers: list[dict], m: GulfOfMexicoValue, key: GulfOfMexicoValue
+) -> GulfOfMexicoValue:
+    """Remove key from the map and return its value (undefined if it was missing)."""
+    import gulfofmexico.interpreter as interpreter
+
+    dict_key = map_key("map_delete", m, key)
+    old = m.self_dict.pop(dict_key, GulfOfMexicoUndefined())
+    interpreter.notify_value_watchers(m, [], when_statement_watchers)
+    return old
+
+
 def db_to_boolean(val: GulfOfMexicoValue) -> GulfOfMexicoBoolean:
     return_bool = None
     match val:
+        case GulfOfMexicoString() if val.value in BOOLEAN_STRINGS:
+            return_bool = BOOLEAN_STRINGS[val.value]
         case GulfOfMexicoString():
             return_bool = bool(val.value.strip()) or (None if len(val.value) else False)
         case (
@@ -654,21 +869,22 @@ def db_to_boolean(val: GulfOfMexicoValue) -> GulfOfMexicoBoolean:
     return GulfOfMexicoBoolean(return_bool)
 
 
-def db_to_string(val: GulfOfMexicoValue) -> GulfOfMexicoString:
+def db_to_string(
+    val: GulfOfMexicoValue, number_mode: str = "plain"
+) -> GulfOfMexicoString:
     return_string = str(val)
     match val:
         case GulfOfMexicoString():
             return_string = val.value
         case GulfOfMexicoList():
-            return_string = (
-                f"[{', '.join([db_to_string(v).value for v in val.values])}]"
-            )
+            items = [db_to_string(v, number_mode).value for v in val.values]
+            return_string = f"[{', '.join(items)}]"
         case GulfOfMexicoBoolean():
             return_string = (
                 "true" if val.value else "maybe" if val.value is None else "false"
             )
         case GulfOfMexicoNumber():
-            return_string = str(val.value)
+            return_string = format_number(val.value, number_mode)
         case GulfOfMexicoFunction():
             return_string = f"<function ({', '.join(val.args)})>"
         case GulfOfMexicoObject():
@@ -678,14 +894,19 @@ def db_to_string(val: GulfOfMexicoValue) -> GulfOfMexicoString:
         case GulfOfMexicoKeyword():
             return_string = val.value
         case GulfOfMexicoMap():
-            return_string = f'{{{", ".join([f"{k}: {db_to_string(v).value}" for k, v in val.self_dict.items()])}}}'
+            return_string = f'{{{", ".join([f"{k}: {db_to_string(v, number_mode).value}" for k, v in val.self_dict.items()])}}}'
     return GulfOfMexicoString(return_string)
 
 
+def display_string(val: GulfOfMexicoValue) -> str:
+    """How print() and the REPL show a value, with numbers in the --numbers mode."""
+    return db_to_string(val, number_display).value
+
+
 def db_print(*vals: GulfOfMexicoValue) -> None:
     import sys
 
-    output = " ".join([db_to_string(v).value for v in vals])
+    output = " ".join([display_string(v) for v in vals])
     print(output)
     sys.stdout.flush()
     sys.stderr.write(f"[DB_PRINT] Called with: {repr(output)}\n")
@@ -697,6 +918,8 @@ def db_to_number(val: GulfOfMexicoValue) -> GulfOfMexicoNumber:
     match val:
         case GulfOfMexicoNumber():
             return_number = val.value
+        case GulfOfMexicoString() if val.value in BOOLEAN_STRINGS:
+            return db_to_number(GulfOfMexicoBoolean(BOOLEAN_STRINGS[val.value]))
         case GulfOfMexicoString():
             return_number = float(val.value)
         case GulfOfMexicoUndefined():
@@ -720,6 +943,93 @@ def db_to_number(val: GulfOfMexicoValue) -> GulfOfMexicoNumber:
     return GulfOfMexicoNumber(return_number)
 
 
+def db_len(
+    namespaces: list[dict[str, Union[Name, Variable]]],
+    when_statement_watchers: list[dict],
+    token: Token,
+    val: GulfOfMexicoValue,
+) -> GulfOfMexicoValue:
+    import gulfofmexico.interpreter as interpreter
+
+    if interpreter.get_object_method(val, method := "__len__"):
+        return interpreter.call_object_method(
+            val, method, [], token, namespaces, when_statement_watchers  # type: ignore
+        )
+    match val:
+        case GulfOfMexicoString():
+            return GulfOfMexicoNumber(len(val.value))
+        case GulfOfMexicoList():
+            return GulfOfMexicoNumber(len(val.values))
+        case GulfOfMexicoMap():
+            return GulfOfMexicoNumber(len(val.self_dict))
+    raise NonFormattedError(f"Cannot take the length of type {type(val).__name__}.")
+
+
+def db_range(*args: GulfOfMexicoValue) -> GulfOfMexicoList:
+    if not 1 <= len(args) <= 3 or not all(
+        isinstance(arg, GulfOfMexicoNumber) for arg in args
+    ):
+        raise NonFormattedError("'range' requires one to three numbers.")
+    nums = [arg.value for arg in args]  # type: ignore
+    start, end, step = 0, nums[0], 1
+    if len(nums) >= 2:
+        start, end = nums[0], nums[1]
+    if len(nums) == 3:
+        step = nums[2]
+    if step == 0:
+        raise NonFormattedError("'range' step cannot be zero.")
+    values: list[GulfOfMexicoValue] = []
+    while start < end if step > 0 else start > end:
+        values.append(GulfOfMexicoNumber(start))
+        start += step
+    return GulfOfMexicoList(values)
+
+
+def db_clamp(
+    x: GulfOfMexicoValue, lo: GulfOfMexicoValue, hi: GulfOfMexicoValue
+) -> GulfOfMexicoValue:
+    """x limited to [lo, hi], or undefined for non-numbers or lo > hi."""
+    if not all(isinstance(v, GulfOfMexicoNumber) for v in (x, lo, hi)):
+        return GulfOfMexicoUndefined()
+    if lo.value > hi.value:  # type: ignore
+        return GulfOfMexicoUndefined()
+    return GulfOfMexicoNumber(min(max(x.value, lo.value), hi.value))  # type: ignore
+
+
+def db_sign(x: GulfOfMexicoValue) -> GulfOfMexicoValue:
+    """-1, 0 or 1 depending on the sign of x, or undefined for non-numbers."""
+    if not isinstance(x, GulfOfMexicoNumber):
+        return GulfOfMexicoUndefined()
+    return GulfOfMexicoNumber((x.value > 0) - (x.value < 0))
+
+
+def db_abs_diff(a: GulfOfMexicoValue, b: GulfOfMexicoValue) -> GulfOfMexicoValue:
+    """|a - b|, or undefined for non-numbers."""
+    if not isinstance(a, GulfOfMexicoNumber) or not isinstance(b, GulfOfMexicoNumber):
+        return GulfOfMexicoUndefined()
+    return GulfOfMexicoNumber(abs(a.value - b.value))
+
+
+def db_enumerate(val: GulfOfMexicoValue) -> GulfOfMexicoList:
+    """[index, value] pairs, with indices starting at -1 like list indexing."""
+    if not isinstance(val, GulfOfMexicoList):
+        raise NonFormattedError("'enumerate' requires a list.")
+    return GulfOfMexicoList(
+        [
+            GulfOfMexicoList([GulfOfMexicoNumber(i), v])
+            for i, v in enumerate(val.values, start=-1)
+        ]
+    )
+
+
+def db_zip(a: GulfOfMexicoValue, b: GulfOfMexicoValue) -> GulfOfMexicoList:
+    if not isinstance(a, GulfOfMexicoList) or not isinstance(b, GulfOfMexicoList):
+        raise NonFormattedError("'zip' requires two lists.")
+    return GulfOfMexicoList(
+        [GulfOfMexicoList([x, y]) for x, y in zip(a.values, b.values)]
+    )
+
+
 def db_signal(starting_value: GulfOfMexicoValue) -> GulfOfMexicoValue:
     obj = Name("", starting_value)
 
@@ -746,6 +1056,66 @@ def db_read(path: GulfOfMexicoValue) -> GulfOfMexicoString:
     return GulfOfMexicoString(s)
 
 
+def db_starts_with(
+    s: GulfOfMexicoValue, prefix: GulfOfMexicoValue
+) -> GulfOfMexicoBoolean:
+    return GulfOfMexicoBoolean(
+        db_to_string(s).value.startswith(db_to_string(prefix).value)
+    )
+
+
+def db_ends_with(
+    s: GulfOfMexicoValue, suffix: GulfOfMexicoValue
+) -> GulfOfMexicoBoolean:
+    return GulfOfMexicoBoolean(
+        db_to_string(s).value.endswith(db_to_string(suffix).value)
+    )
+
+
+def db_trim(s: GulfOfMexicoValue) -> GulfOfMexicoString:
+    return GulfOfMexicoString(db_to_string(s).value.strip())
+
+
+def db_replace(
+    s: GulfOfMexicoValue, old: GulfOfMexicoValue, new: GulfOfMexicoValue
+) -> GulfOfMexicoString:
+    """Replace every occurrence of old. An empty old leaves the string unchanged."""
+    text, old_str = db_to_string(s).value, db_to_string(old).value
+    if not old_str:
+        return GulfOfMexicoString(text)
+    return GulfOfMexicoString(text.replace(old_str, db_to_string(new).value))
+
+
+def db_char_at(s: GulfOfMexicoValue, index: GulfOfMexicoValue) -> GulfOfMexicoValue:
+    """Character at a string index (first is -1), or undefined when out of range."""
+    if not isinstance(index, GulfOfMexicoNumber):
+        raise NonFormattedError("'char_at' requires a number index.")
+    text = s if isinstance(s, GulfOfMexicoString) else db_to_string(s)
+    try:
+        return text.access_index(index)
+    except NonFormattedError:
+        return GulfOfMexicoUndefined()
+
+
+def db_char_code(s: GulfOfMexicoValue) -> GulfOfMexicoNumber:
+    text = db_to_string(s).value
+    if not text:
+        raise NonFormattedError("'char_code' requires a non-empty string.")
+    return GulfOfMexicoNumber(ord(text[0]))
+
+
+def db_from_char_code(n: GulfOfMexicoValue) -> GulfOfMexicoString:
+    if (
+        not isinstance(n, GulfOfMexicoNumber)
+        or not is_int(n.value)
+        or not 0 <= n.value <= sys.maxunicode
+    ):
+        raise NonFormattedError(
+            f"'from_char_code' requires a whole number from 0 to {sys.maxunicode}."
+        )
+    return GulfOfMexicoString(chr(round(n.value)))
+
+
 def db_regex_match(arg: GulfOfMexicoString) -> GulfOfMexicoBoolean:
     if not isinstance(arg, GulfOfMexicoString):
         raise NonFormattedError("regex_match requires pattern,string")
@@ -799,6 +1169,36 @@ def db_regex_replace(arg: GulfOfMexicoString) -> GulfOfMexicoString:
         raise NonFormattedError(f"Invalid regex pattern: {e}")
 
 
+def db_format(
+    template: GulfOfMexicoValue, *args: GulfOfMexicoValue
+) -> GulfOfMexicoString:
+    """Replace {0}, {1}, ... in the template with the matching argument.
+
+    Placeholders without a matching argument are left untouched, and
+    {{ / }} produce literal braces.
+    """
+    if not isinstance(template, GulfOfMexicoString):
+        raise NonFormattedError("'format' requires the template to be a string.")
+    text, result, i = template.value, "", 0
+    while i < len(text):
+        if text[i : i + 2] in ("{{", "}}"):
+            result += text[i]
+            i += 2
+            continue
+        if text[i] == "{" and (end := text.find("}", i)) != -1:
+            index_str = text[i + 1 : end]
+            if index_str.isdigit():
+                if int(index_str) < len(args):
+                    result += db_to_string(args[int(index_str)]).value
+                else:
+                    result += text[i : end + 1]
+                i = end + 1
+                continue
+        result += text[i]
+        i += 1
+    return GulfOfMexicoString(result)
+
+
 def db_write(path: GulfOfMexicoValue, content: GulfOfMexicoValue) -> None:
     if not isinstance(path, GulfOfMexicoString):
         raise NonFormattedError("'write' requires path to be a string")
@@ -811,15 +1211,147 @@ def db_exit() -> None:
     exit()
 
 
+def db_line_count() -> GulfOfMexicoNumber:
+    import gulfofmexico.interpreter as interpreter
+
+    return GulfOfMexicoNumber(interpreter.current_line)
+
+
+def db_args() -> GulfOfMexicoList:
+    import gulfofmexico.interpreter as interpreter
+
+    return GulfOfMexicoList([GulfOfMexicoString(a) for a in interpreter.script_args])
+
+
+def db_last_when() -> GulfOfMexicoNumber:
+    import gulfofmexico.interpreter as interpreter
+
+    return GulfOfMexicoNumber(interpreter.last_when_handle)
+
+
+def db_stop_when(handle: GulfOfMexicoValue) -> GulfOfMexicoBoolean:
+    import gulfofmexico.interpreter as interpreter
+
+    if not isinstance(handle, GulfOfMexicoNumber) or not is_int(handle.value):
+        raise NonFormattedError("'stop_when' requires a handle from last_when().")
+    return GulfOfMexicoBoolean(interpreter.stop_when(int(handle.value)))
+
+
+def db_eval(
+    namespaces: list[dict[str, Union[Name, Variable]]], source: GulfOfMexicoValue
+) -> Optional[GulfOfMexicoValue]:
+    import gulfofmexico.interpreter as interpreter
+
+    if not isinstance(source, GulfOfMexicoString):
+        raise NonFormattedError("'eval' requires the code to run as a string.")
+    return interpreter.evaluate_source(source.value, namespaces)
+
+
+def db_import_value(path: GulfOfMexicoValue) -> Optional[GulfOfMexicoValue]:
+    import gulfofmexico.interpreter as interpreter
+
+    if not isinstance(path, GulfOfMexicoString):
+        raise NonFormattedError("'import_value' requires a file path as a string.")
+    try:
+        with open(path.value, encoding="utf-8") as f:
+            source = f.read()
+    except OSError as e:
+        raise NonFormattedError(f"Cannot read {path.value}: {e.strerror}.")
+    return interpreter.evaluate_file_isolated(path.value, source)
+
+
+def db_lifetime(
+    namespaces: list[dict[str, Union[Name, Variable]]], name: GulfOfMexicoValue
+) -> GulfOfMexicoValue:
+    """Lines (or seconds, for <Ns> lifetimes) left before a variable expires."""
+    import gulfofmexico.interpreter as interpreter
+
+    if not isinstance(name, GulfOfMexicoString):
+        raise NonFormattedError("'lifetime' requires the variable name as a string.")
+    for namespace in reversed(namespaces):
+        if name.value in namespace:
+            var = namespace[name.value]
+            break
+    else:
+        return GulfOfMexicoUndefined()
+    if not isinstance(var, Variable) or not var.lifetimes:
+        return GulfOfMexicoUndefined()
+    current = var.lifetimes[0]
+    if current.is_temporal:
+        elapsed = time.time() - current.creation_time
+        return GulfOfMexicoNumber(max(current.temporal_duration - elapsed, 0.0))
+    if current.lines_left >= INFINITE_LIFETIME:
+        return GulfOfMexicoUndefined()
+    elapsed_lines = interpreter.current_line - current.creation_line
+    return GulfOfMexicoNumber(max(current.lines_left - elapsed_lines, 0))
+
+
+def db_restore_builtin(
+    namespaces: list[dict[str, Union[Name, Variable]]], name: GulfOfMexicoValue
+) -> None:
+    """Undo every declaration shadowing a builtin so the original is used again."""
+    if not isinstance(name, GulfOfMexicoString):
+        raise NonFormattedError("'restore_builtin' requires the name as a string.")
+    if name.value not in KEYWORDS:
+        raise NonFormattedError(f"There is no builtin named {name.value}.")
+    for namespace in namespaces[1:]:
+        namespace.pop(name.value, None)
+    namespaces[0][name.value] = KEYWORDS[name.value]
+
+
+def db_builtins(namespaces: list[dict[str, Union[Name, Variable]]]) -> GulfOfMexicoList:
+    """Sorted [name, argument count] pairs for the builtin functions in scope.
+
+    An argument count of -1 means the function takes any number of arguments.
+    """
+    return GulfOfMexicoList(
+        [
+            GulfOfMexicoList(
+                [GulfOfMexicoString(name), GulfOfMexicoNumber(entry.value.arg_count)]
+            )
+            for name, entry in sorted(namespaces[0].items())
+            if isinstance(entry, Name) and isinstance(entry.value, BuiltinFunction)
+        ]
+    )
+
+
+def db_getenv(name: GulfOfMexicoValue) -> GulfOfMexicoValue:
+    if not isinstance(name, GulfOfMexicoString):
+        raise NonFormattedError("'getenv' requires the variable name to be a string.")
+    value = os.environ.get(name.value)
+    return GulfOfMexicoUndefined() if value is None else GulfOfMexicoString(value)
+
+
+def db_setenv(name: GulfOfMexicoValue, value: GulfOfMexicoValue) -> None:
+    """Set an environment variable for this process and any it starts."""
+    if not isinstance(name, GulfOfMexicoString):
+        raise NonFormattedError("'setenv' requires the variable name to be a string.")
+    os.environ[name.value] = db_to_string(value).value
+
+
+# math functions that give undefined instead of an error for non-numbers or inputs
+# outside their domain, like log(0)
+UNDEFINED_ON_BAD_INPUT = frozenset({"sin", "cos", "tan", "log", "log10", "exp"})
+
+
 def __math_function_decorator(func: Callable):
+    lenient = func.__name__ in UNDEFINED_ON_BAD_INPUT
+
     @functools.wraps(func)
-    def inner(*args) -> GulfOfMexicoNumber:  # no kwargs
+    def inner(*args) -> GulfOfMexicoValue:  # no kwargs
         for arg in args:
             if not isinstance(arg, GulfOfMexicoNumber):
+                if lenient:
+                    return GulfOfMexicoUndefined()
                 raise NonFormattedError(
                     "Cannot pass in a non-number value into a math function."
                 )
-        return GulfOfMexicoNumber(func(*[arg.value for arg in args]))
+        try:
+            return GulfOfMexicoNumber(func(*[arg.value for arg in args]))
+        except (ValueError, OverflowError) as e:
+            if lenient:
+                return GulfOfMexicoUndefined()
+            raise NonFormattedError(f"'{func.__name__}' failed: {e}.")
 
     return inner
 
@@ -883,19 +1415,64 @@ MATH_FUNCTION_KEYWORDS = {
 BUILTIN_FUNCTION_KEYWORDS = {
     "new": Name("new", BuiltinFunction(1, db_new)),
     "current": Name("current", BuiltinFunction(1, db_identity)),
+    "coalesce": Name("coalesce", BuiltinFunction(2, db_coalesce)),
+    "xor": Name("xor", BuiltinFunction(2, db_xor)),
+    "nand": Name("nand", BuiltinFunction(2, db_nand)),
     "Map": Name("Map", BuiltinFunction(0, db_map)),
+    "map_set": Name("map_set", BuiltinFunction(3, db_map_set, takes_watchers=True)),
+    "map_get": Name("map_get", BuiltinFunction(2, db_map_get)),
+    "map_delete": Name(
+        "map_delete", BuiltinFunction(2, db_map_delete, takes_watchers=True)
+    ),
     "Boolean": Name("Boolean", BuiltinFunction(1, db_to_boolean)),
     "String": Name("String", BuiltinFunction(1, db_to_string)),
     "print": Name("print", BuiltinFunction(-1, db_print)),
     "exit": Name("exit", BuiltinFunction(0, db_exit)),
+    "line_count": Name("line_count", BuiltinFunction(0, db_line_count)),
+    "args": Name("args", BuiltinFunction(0, db_args)),
+    "last_when": Name("last_when", BuiltinFunction(0, db_last_when)),
+    "stop_when": Name("stop_when", BuiltinFunction(1, db_stop_when)),
+    "lifetime": Name(
+        "lifetime", BuiltinFunction(1, db_lifetime, takes_namespaces=True)
+    ),
+    "builtins": Name(
+        "builtins", BuiltinFunction(0, db_builtins, takes_namespaces=True)
+    ),
+    "restore_builtin": Name(
+        "restore_builtin", BuiltinFunction(1, db_restore_builtin, takes_namespaces=True)
+    ),
+    "eval": Name("eval", BuiltinFunction(1, db_eval, takes_namespaces=True)),
+    "import_value": Name("import_value", BuiltinFunction(1, db_import_value)),
+    "getenv": Name("getenv", BuiltinFunction(1, db_getenv)),
+    "setenv": Name("setenv", BuiltinFunction(2, db_setenv)),
     "Number": Name("Number", BuiltinFunction(1, db_to_number)),
+    "len": Name(
+        "len",
+        BuiltinFunction(
+            1, db_len, takes_namespaces=True, takes_watchers=True, takes_token=True
+        ),
+    ),
+    "range": Name("range", BuiltinFunction(-1, db_range)),
+    "clamp": Name("clamp", BuiltinFunction(3, db_clamp)),
+    "sign": Name("sign", BuiltinFunction(1, db_sign)),
+    "abs_diff": Name("abs_diff", BuiltinFunction(2, db_abs_diff)),
+    "enumerate": Name("enumerate", BuiltinFunction(1, db_enumerate)),
+    "zip": Name("zip", BuiltinFunction(2, db_zip)),
     "use": Name("use", BuiltinFunction(1, db_signal)),
     "sleep": Name("sleep", BuiltinFunction(1, db_sleep)),
     "read": Name("read", BuiltinFunction(-1, db_read)),
     "write": Name("write", BuiltinFunction(-1, db_write)),
+    "starts_with": Name("starts_with", BuiltinFunction(2, db_starts_with)),
+    "ends_with": Name("ends_with", BuiltinFunction(2, db_ends_with)),
+    "trim": Name("trim", BuiltinFunction(1, db_trim)),
+    "replace": Name("replace", BuiltinFunction(3, db_replace)),
+    "char_at": Name("char_at", BuiltinFunction(2, db_char_at)),
+    "char_code": Name("char_code", BuiltinFunction(1, db_char_code)),
+    "from_char_code": Name("from_char_code", BuiltinFunction(1, db_from_char_code)),
     "regex_match": Name("regex_match", BuiltinFunction(1, db_regex_match)),
     "regex_findall": Name("regex_findall", BuiltinFunction(1, db_regex_findall)),
     "regex_replace": Name("regex_replace", BuiltinFunction(1, db_regex_replace)),
+    "format": Name("format", BuiltinFunction(-1, db_format)),
 }
 BUILTIN_VALUE_KEYWORDS = {
     "true": Name("true", GulfOfMexicoBoolean(True)),
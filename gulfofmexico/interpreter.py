@@ -31,24 +31,36 @@ that is NOT used in production. All code execution uses this file's pattern matc
 from __future__ import annotations
 import os
 import re
+import math
+import importlib.util
 import sys
 import json
 import locale
 import random
 import pickle
 import requests
-from time import sleep
+from time import monotonic, sleep
 from pathlib import Path
 from copy import deepcopy
+from dataclasses import dataclass, field
 from threading import Thread
+from contextlib import contextmanager
 from difflib import SequenceMatcher
-from typing import Literal, Optional, TypeAlias, Union
+from typing import (
+    TYPE_CHECKING,
+    Callable,
+    Iterable,
+    Iterator,
+    Literal,
+    Optional,
+    TypeAlias,
+    Union,
+)
 
-KEY_MOUSE_IMPORTED = True
-try:
+# pynput is only imported once an after statement needs input events (see start_input_listeners)
+KEY_MOUSE_IMPORTED = importlib.util.find_spec("pynput") is not None
+if TYPE_CHECKING:
     from pynput import keyboard, mouse
-except ImportError:
-    KEY_MOUSE_IMPORTED = False
 
 GITHUB_IMPORTED = True
 try:
@@ -57,11 +69,14 @@ except ImportError:
     GITHUB_IMPORTED = False
 
 from gulfofmexico.base import (
+    ESCAPE_PATTERN,
     InterpretationError,
     NonFormattedError,
     OperatorType,
     Token,
     TokenType,
+    TypeMismatchError,
+    UndefinedNameError,
     debug_print,
     debug_print_no_token,
     raise_error_at_line,
@@ -69,6 +84,8 @@ from gulfofmexico.base import (
 )
 from gulfofmexico.builtin import (
     FLOAT_TO_INT_PREC,
+    KEYWORDS,
+    RESERVED_WORDS,
     BuiltinFunction,
     GulfOfMexicoBoolean,
     GulfOfMexicoFunction,
@@ -89,12 +106,13 @@ from gulfofmexico.builtin import (
     GulfOfMexicoValue,
     VariableLifetime,
     db_not,
+    db_print,
     db_to_boolean,
     db_to_number,
     db_to_string,
     is_int,
 )
-from gulfofmexico.serialize import serialize_obj, deserialize_obj
+from gulfofmexico.serialize import serialize_obj, deserialize_obj, value_to_source
 from gulfofmexico.processor.lexer import tokenize as db_tokenize
 from gulfofmexico.processor.expression_tree import (
     ExpressionTreeNode,
@@ -113,16 +131,20 @@ from gulfofmexico.processor.syntax_tree import (
     CodeStatement,
     CodeStatementKeywordable,
     Conditional,
+    DebugStatement,
     DeleteStatement,
     ExportStatement,
     ExpressionStatement,
     FunctionDefinition,
     ImportStatement,
+    MatchStatement,
+    RepeatStatement,
     ReturnStatement,
     ReverseStatement,
     VariableAssignment,
     VariableDeclaration,
     WhenStatement,
+    generate_syntax_tree,
 )
 
 # several "ratios" used in the approx equal function
@@ -197,6 +219,17 @@ def get_modified_prev_name(name: str) -> str:
     return f"{name.replace('.', '__')}__prev"
 
 
+def function_namespaces(
+    func: GulfOfMexicoFunction, namespaces: list[Namespace]
+) -> list[Namespace]:
+    """The caller's scopes, then the scopes a nested function closed over."""
+    if func.closure is None:
+        return namespaces
+    return namespaces + [
+        ns for ns in func.closure if not any(ns is caller for caller in namespaces)
+    ]
+
+
 # i believe this function is exclusively called from the evaluate_expression function
 def evaluate_normal_function(
     expr: FunctionNode,
@@ -216,6 +249,15 @@ def evaluate_normal_function(
                 expr.name,
             )
         max_arg_count = func.arg_count if func.arg_count >= 0 else len(args)
+        if func.takes_token:
+            args = [expr.name, *args]  # type: ignore
+            max_arg_count += 1
+        if func.takes_watchers:
+            args = [when_statement_watchers, *args]  # type: ignore
+            max_arg_count += 1
+        if func.takes_namespaces:
+            args = [namespaces, *args]  # type: ignore
+            max_arg_count += 1
         return func.function(*args[:max_arg_count]) or GulfOfMexicoUndefined()
 
     # check length is proper, adjust namespace, and run this code
@@ -229,17 +271,35 @@ def evaluate_normal_function(
     new_namespace: Namespace = {
         name: Name(name, arg) for name, arg in zip(func.args, args)
     }
-    return (
-        interpret_code_statements(
-            func.code,
-            namespaces + [new_namespace],
-            [],
-            when_statement_watchers + [{}],
-            {},
-            [],
+    call_stack.append((expr.name.value, expr.name.line))
+    try:
+        result = (
+            interpret_code_statements(
+                func.code,
+                function_namespaces(func, namespaces) + [new_namespace],
+                [],
+                when_statement_watchers + [{}],
+                {},
+                [],
+            )
+            or GulfOfMexicoUndefined()
         )
-        or GulfOfMexicoUndefined()
-    )
+        return coerce_return_value(result, func.return_type)
+    except InterpretationError as e:
+        attach_call_stack(e)
+        raise
+    finally:
+        call_stack.pop()
+
+
+def attach_call_stack(error: InterpretationError) -> None:
+    """Record the active calls on an error the first time it leaves a function."""
+    if error.call_stack:
+        return
+    error.call_stack = list(call_stack)
+    if show_call_stack:
+        frames = "\n".join(f"  {name} (line {line})" for name, line in call_stack)
+        error.args = (f"{error}\n\nCall stack (most recent call last):\n{frames}",)
 
 
 def register_async_function(
@@ -257,10 +317,10 @@ def register_async_function(
             f"Expected more arguments for function call with {len(func.args)} argument{'s' if len(func.args) != 1 else ''}.",
             expr.name,
         )
-    function_namespaces = namespaces + [
+    call_namespaces = function_namespaces(func, namespaces) + [
         {name: Name(name, arg) for name, arg in zip(func.args, args)}
     ]
-    async_statements.append((func.code, function_namespaces, 0, 1))
+    async_statements.append((func.code, call_namespaces, 0, 1))
 
 
 def get_code_from_when_statement_watchers(
@@ -269,23 +329,39 @@ def get_code_from_when_statement_watchers(
     vals = []
     for watcher_dict in when_statement_watchers:
         if val := watcher_dict.get(name_or_id):
-            vals += val
+            vals += [w for w in val if id(w[0]) not in stopped_when_conditions]
     return vals
 
 
+def add_to_when_statement_watchers(
+    name_or_id: Union[str, int],
+    watchers: list[tuple[ExpressionTreeNode, list[tuple[CodeStatement, ...]]]],
+    when_statement_watchers: WhenStatementWatchers,
+) -> None:
+    """Registers watchers under name_or_id in the innermost scope, once each.
+
+    Watcher tuples are shared rather than copied, so they are matched by identity
+    instead of comparing their conditions, bodies and scope chains field by field.
+    """
+    if not watchers:
+        return
+    registered = when_statement_watchers[-1].setdefault(name_or_id, [])
+    present = {id(w) for w in registered}
+    for watcher in watchers:
+        if id(watcher) not in present:
+            present.add(id(watcher))
+            registered.append(watcher)
+
+
 def remove_from_when_statement_watchers(
     name_or_id: Union[str, int],
-    watcher: tuple[ExpressionTreeNode, list[tuple[CodeStatement, ...]]],
+    watchers: list[tuple[ExpressionTreeNode, list[tuple[CodeStatement, ...]]]],
     when_statement_watchers: WhenStatementWatchers,
 ) -> None:
+    removed = {id(w) for w in watchers}
     for watcher_dict in when_statement_watchers:
         if vals := watcher_dict.get(name_or_id):
-            remove = None
-            for i, v in enumerate(vals):
-                if v == watcher:
-                    remove = i
-            if remove is not None:
-                del vals[remove]
+            vals[:] = [v for v in vals if id(v) not in removed]
 
 
 def remove_from_all_when_statement_watchers(
@@ -297,6 +373,8 @@ def remove_from_all_when_statement_watchers(
 
 
 def load_global_gulfofmexico_variables(namespaces: list[Namespace]) -> None:
+    if not load_persisted_globals:
+        return
 
     dir_path = Path().home() / DB_RUNTIME_PATH
     inf_values_path = dir_path / INF_VAR_VALUES_PATH
@@ -342,6 +420,8 @@ def load_global_gulfofmexico_variables(namespaces: list[Namespace]) -> None:
 
 def load_local_immutable_constants(namespaces: list[Namespace]) -> None:
     """Load locally stored immutable constants (const const const variables)."""
+    if not load_persisted_globals:
+        return
     dir_path = Path().home() / DB_RUNTIME_PATH
     immutable_values_path = dir_path / IMMUTABLE_CONSTANTS_VALUES_PATH
     immutable_list = dir_path / IMMUTABLE_CONSTANTS_PATH
@@ -412,6 +492,9 @@ def save_local_immutable_constant(
 
 
 def load_public_global_variables(namespaces: list[Namespace]) -> None:
+    if not load_persisted_globals:
+        return
+
     # First load locally stored immutable constants
     load_local_immutable_constants(namespaces)
 
@@ -497,33 +580,74 @@ def check_type_annotation(value: GulfOfMexicoValue, type_tokens: list[Token]) ->
 
     # Check type compatibility
     if type_name == "Int":
-        if not isinstance(value, GulfOfMexicoNumber):
-            raise InterpretationError(
-                f"Type error: expected Int, got {type(value).__name__}"
-            )
+        expected_type = GulfOfMexicoNumber
     elif type_name == "String":
-        if not isinstance(value, GulfOfMexicoString):
-            raise InterpretationError(
-                f"Type error: expected String, got {type(value).__name__}"
-            )
+        expected_type = GulfOfMexicoString
     elif type_name == "Char[]":
-        if not isinstance(value, GulfOfMexicoString):
-            raise InterpretationError(
-                f"Type error: expected Char[], got {type(value).__name__}"
-            )
+        expected_type = GulfOfMexicoString
     elif type_name == "Int9":
-        if not isinstance(value, GulfOfMexicoNumber):
-            raise InterpretationError(
-                f"Type error: expected Int9, got {type(value).__name__}"
-            )
         # Int9 represents binary, but for now we'll just check it's a number
+        expected_type = GulfOfMexicoNumber
     elif type_name == "Int99":
-        if not isinstance(value, GulfOfMexicoNumber):
-            raise InterpretationError(
-                f"Type error: expected Int99, got {type(value).__name__}"
-            )
         # Int99 represents some other representation, but for now we'll just check it's a number
+        expected_type = GulfOfMexicoNumber
     # Add more type checks as needed
+    else:
+        return
+    if not isinstance(value, expected_type):
+        got = type(value).__name__
+        raise TypeMismatchError(
+            f"Type error: expected {type_name}, got {got}",
+            expected=type_name,
+            got=got,
+        )
+
+
+# declared return types and the conversion applied to a function's result
+RETURN_TYPE_COERCIONS: dict[str, Callable[[GulfOfMexicoValue], GulfOfMexicoValue]] = {
+    "Number": db_to_number,
+    "Int": db_to_number,
+    "String": db_to_string,
+    "Char[]": db_to_string,
+    "Boolean": db_to_boolean,
+}
+
+
+def coerce_return_value(
+    value: GulfOfMexicoValue, return_type: Optional[str]
+) -> GulfOfMexicoValue:
+    """Convert a function result to its declared return type, if it is a known one."""
+    coerce = RETURN_TYPE_COERCIONS.get(return_type or "")
+    if coerce is None:
+        return value
+    try:
+        return coerce(value)
+    except ValueError:
+        raise NonFormattedError(
+            f"Cannot turn {db_to_string(value).value} into a {return_type}."
+        )
+
+
+def warn_if_shadowing(name_token: Token, namespaces: list[Namespace]) -> None:
+    """Warn on stderr when a declaration hides a builtin or an outer const."""
+    name = name_token.value
+    builtin = KEYWORDS.get(name)
+    if (
+        builtin is not None
+        and not isinstance(builtin.value, GulfOfMexicoKeyword)
+        and namespaces[0].get(name) is builtin
+    ):
+        shadowed = "a builtin"
+    elif any(
+        isinstance(v := ns.get(name), Variable) and v.lifetimes and not v.can_be_reset
+        for ns in namespaces[:-1]
+    ):
+        shadowed = "a const from an outer scope"
+    else:
+        return
+    sys.stderr.write(
+        f"{filename}, line {name_token.line}: warning: {name} shadows {shadowed}\n"
+    )
 
 
 def declare_new_variable(
@@ -536,6 +660,15 @@ def declare_new_variable(
     name = statement.name.value
     confidence = statement.confidence
     lifetime = statement.lifetime
+    if name in RESERVED_WORDS and isinstance(value, GulfOfMexicoKeyword):
+        raise_error_at_token(
+            filename,
+            code,
+            f"Cannot alias {name}: it is reserved by the parser.",
+            statement.name,
+        )
+    if warn_on_shadowing:
+        warn_if_shadowing(statement.name, namespaces)
 
     # Determine variable properties based on modifiers
     can_be_reset = "var" in [mod.value for mod in statement.modifiers]
@@ -563,8 +696,13 @@ def declare_new_variable(
                 statement.name,
             )
 
-    # Create the variable
-    var = Variable(name, [], [])
+    # Redeclaring a name in the same scope layers a new lifetime on top, so
+    # the old value comes back once the new one expires
+    existing = namespaces[-1].get(name)
+    if isinstance(existing, Variable) and existing.lifetimes:
+        var = existing
+    else:
+        var = Variable(name, [], [])
     var.add_lifetime(
         value,
         confidence,
@@ -573,10 +711,14 @@ def declare_new_variable(
         can_edit_value,
         is_temporal=is_temporal,
         temporal_duration=temporal_duration,
+        creation_line=current_line,
     )
 
     # Add to namespace
+    forget_name_lookups(name)
     namespaces[-1][name] = var
+    if var.expires and (namespaces[-1], var) not in expiring_variables:
+        expiring_variables.append((namespaces[-1], var))
 
     # Check type annotation if provided
     if statement.type_annotation:
@@ -600,29 +742,50 @@ def declare_new_variable(
             # This is acceptable - the variable is still immutable locally
             pass
 
-    # Trigger when statement watchers for this new variable
+    # Trigger when statement watchers for this new variable. A when registered
+    # before this name existed watches the name itself (see register_when_statement)
     when_watchers = get_code_from_when_statement_watchers(
         id(var), when_statement_watchers
-    )
+    ) + get_code_from_when_statement_watchers(name, when_statement_watchers)
+    add_to_when_statement_watchers(id(var), when_watchers, when_statement_watchers)
+    if isinstance(value, GulfOfMexicoMutable):
+        add_to_when_statement_watchers(
+            id(value), when_watchers, when_statement_watchers
+        )
     for when_watcher in when_watchers:
-        condition, inside_statements = when_watcher
+        condition, inside_statements, captured_namespaces = when_watcher
+        if has_undefined_names(condition, captured_namespaces):
+            continue
         condition_val = evaluate_expression(
-            condition, namespaces, async_statements, when_statement_watchers
+            condition, captured_namespaces, async_statements, when_statement_watchers
         )
-        if isinstance(value, GulfOfMexicoMutable):
-            if id(value) not in when_statement_watchers[-1]:
-                when_statement_watchers[-1][id(value)] = []
-            when_statement_watchers[-1][id(value)].append(when_watcher)
         execute_conditional(
             condition_val,
             inside_statements,
-            namespaces,
+            captured_namespaces,
             when_statement_watchers,
             {},
             [],
         )
 
 
+def cleanup_expired_variables() -> None:
+    """Expire lifetimes that have run out as of the current line.
+
+    A variable whose top lifetime expires falls back to the one beneath it; a
+    variable with nothing left is removed from its namespace.
+    """
+    still_expiring = []
+    for namespace, var in expiring_variables:
+        var.clear_outdated_lifetimes(current_line)
+        if not var.lifetimes:
+            if namespace.get(var.name) is var:
+                del namespace[var.name]
+        elif var.expires:
+            still_expiring.append((namespace, var))
+    expiring_variables[:] = still_expiring
+
+
 def assign_variable(
     statement: VariableAssignment,
     indexes: list[GulfOfMexicoValue],
@@ -721,7 +884,7 @@ def assign_variable(
                 statement.name,
             )
 
-    visited_whens = []
+    visited_whens = set()
     if indexes:
 
         # goes down the list until it can assign something in the list
@@ -738,37 +901,25 @@ def assign_variable(
                     name_token.line,
                     "Attempted to index into an un-indexable object.",
                 )
-            index = remaining_indexes.pop(0)
+            index = to_native_index(value_to_modify, remaining_indexes[0])
+            remaining_indexes = remaining_indexes[1:]
 
-            if not remaining_indexes:  # perform actual assignment here
-                value_to_modify.assign_index(index, new_value)
-            else:
-                assign_variable_helper(
-                    value_to_modify.access_index(index), remaining_indexes
-                )
+            try:
+                if not remaining_indexes:  # perform actual assignment here
+                    value_to_modify.assign_index(index, new_value)
+                else:
+                    inner_value = value_to_modify.access_index(index)
+            except NonFormattedError as e:
+                raise_error_at_line(filename, code, name_token.line, str(e))
+            if remaining_indexes:
+                assign_variable_helper(inner_value, remaining_indexes)
             # check for some watchers here too!!!!!!!!!!!
-            when_watchers = get_code_from_when_statement_watchers(
-                id(value_to_modify), when_statement_watchers
+            notify_value_watchers(
+                value_to_modify,
+                async_statements,
+                when_statement_watchers,
+                visited_whens,
             )
-            for when_watcher in when_watchers:  # i just wanna be done with this :(
-                if any([when_watcher == x for x in visited_whens]):
-                    continue
-                (condition, inside_statements, captured_namespaces) = when_watcher
-                condition_val = evaluate_expression(
-                    condition,
-                    captured_namespaces,
-                    async_statements,
-                    when_statement_watchers,
-                )
-                execute_conditional(
-                    condition_val,
-                    inside_statements,
-                    captured_namespaces,
-                    when_statement_watchers,
-                    {},
-                    [],
-                )
-                visited_whens.append(when_watcher)
 
         # Note: For indexed assignment (e.g., list[0] = x), we don't check can_edit_value
         # because const var allows modifying elements, just not replacing the entire value
@@ -791,6 +942,7 @@ def assign_variable(
             container_val, key = dotted_target
             existing = container_val.namespace.get(key)
             if existing is None:
+                forget_name_lookups(key)
                 container_val.namespace[key] = Name(key, new_value)
             elif isinstance(existing, Variable):
                 if not existing.can_be_reset:
@@ -800,15 +952,7 @@ def assign_variable(
                         "Attempted to set a variable that cannot be set.",
                         name_token,
                     )
-                existing.add_lifetime(
-                    new_value,
-                    confidence,
-                    100000000000,
-                    existing.can_be_reset,
-                    existing.can_edit_value,
-                    is_temporal=False,
-                    temporal_duration=0.0,
-                )
+                existing.reassign(new_value, confidence, current_line)
             else:  # Name
                 existing.value = new_value  # type: ignore[attr-defined]
         else:
@@ -826,21 +970,14 @@ def assign_variable(
                     "Attempted to set a variable that cannot be set.",
                     name_token,
                 )
-            var.add_lifetime(
-                new_value,
-                confidence,
-                100000000000,
-                var.can_be_reset,
-                var.can_edit_value,
-                is_temporal=False,
-                temporal_duration=0.0,
-            )
+            var.reassign(new_value, confidence, current_line)
 
     # check if there is a watcher for this name
     watchers_key = (name, id(namespaces[-1]))
     if watcher := name_watchers.get(watchers_key):
         st, stored_nexts, watcher_ns, promise = watcher
         mod_name = get_modified_next_name(*watchers_key)
+        forget_name_lookups(mod_name)
         watcher_ns[-1][mod_name] = Name(
             mod_name, new_value
         )  # add the value to the uppermost namespace
@@ -854,9 +991,27 @@ def assign_variable(
         del name_watchers[watchers_key]  # stop watching this name
 
     # check if this name appears in a when statement of the appropriate scope  --  it would have to be watching the name
-    if when_watchers := get_code_from_when_statement_watchers(
-        id(var), when_statement_watchers
+    # (an indexed assignment changed the value in place, and its watchers ran above)
+    if not indexes and (
+        when_watchers := get_code_from_when_statement_watchers(
+            id(var), when_statement_watchers
+        )
     ):
+        # move every watcher over to the new value in one pass per key rather than
+        # rescanning the watcher lists once per when
+        if isinstance(new_value, GulfOfMexicoMutable):
+            add_to_when_statement_watchers(
+                id(new_value), when_watchers, when_statement_watchers
+            )
+        if isinstance(
+            var.prev_values[-1], GulfOfMexicoMutable
+        ):  # if prev value was being observed under this statement, remove it
+            remove_from_when_statement_watchers(
+                id(var.prev_values[-1]), when_watchers, when_statement_watchers
+            )
+        add_to_when_statement_watchers(
+            id(var), when_watchers, when_statement_watchers
+        )  # put this where the new variable is
         for when_watcher in when_watchers:  # i just wanna be done with this :(
             if len(when_watcher) == 3:
                 condition, inside_statements, captured_namespaces = when_watcher
@@ -869,27 +1024,6 @@ def assign_variable(
                 async_statements,
                 when_statement_watchers,
             )
-            if isinstance(new_value, GulfOfMexicoMutable):
-                if id(new_value) not in when_statement_watchers[-1]:
-                    when_statement_watchers[-1][id(new_value)] = []
-                if when_watcher not in when_statement_watchers[-1][id(new_value)]:
-                    when_statement_watchers[-1][id(new_value)].append(
-                        when_watcher
-                    )  # remember: this is tuple so it is immutable and copied!
-            if isinstance(
-                var.prev_values[-1], GulfOfMexicoMutable
-            ):  # if prev value was being observed under this statement, remove it
-                remove_from_when_statement_watchers(
-                    id(var.prev_values[-1]),
-                    when_watcher,
-                    when_statement_watchers,
-                )
-            if id(var) not in when_statement_watchers[-1]:
-                when_statement_watchers[-1][id(var)] = []
-            if when_watcher not in when_statement_watchers[-1][id(var)]:
-                when_statement_watchers[-1][id(var)].append(
-                    when_watcher
-                )  # put this where the new variable is
             execute_conditional(
                 condition_val,
                 inside_statements,
@@ -901,6 +1035,39 @@ def assign_variable(
         # Removed: remove_from_all_when_statement_watchers(id(var), when_statement_watchers)
 
 
+def notify_value_watchers(
+    value: GulfOfMexicoValue,
+    async_statements: AsyncStatements,
+    when_statement_watchers: WhenStatementWatchers,
+    visited_whens: Optional[set[int]] = None,
+) -> None:
+    """Re-checks the whens watching a value that was changed in place.
+
+    Whens already in visited_whens are skipped, and the ones that run are added to
+    it, so a nested assignment checks each when once."""
+    if visited_whens is None:
+        visited_whens = set()
+    when_watchers = get_code_from_when_statement_watchers(
+        id(value), when_statement_watchers
+    )
+    for when_watcher in when_watchers:
+        if id(when_watcher) in visited_whens:
+            continue
+        condition, inside_statements, captured_namespaces = when_watcher
+        condition_val = evaluate_expression(
+            condition, captured_namespaces, async_statements, when_statement_watchers
+        )
+        execute_conditional(
+            condition_val,
+            inside_statements,
+            captured_namespaces,
+            when_statement_watchers,
+            {},
+            [],
+        )
+        visited_whens.add(id(when_watcher))
+
+
 def perform_single_value_operation(
     val: GulfOfMexicoValue, operator_token: Token
 ) -> GulfOfMexicoValue:
@@ -919,6 +1086,9 @@ def perform_single_value_operation(
                         code,
                         f"Cannot negate a value of type {type(val).__name__}",
                         operator_token,
+                        TypeMismatchError,
+                        expected="Number, List or String",
+                        got=type(val).__name__,
                     )
         case TokenType.SEMICOLON:
             val_bool = db_to_boolean(val)
@@ -1154,52 +1324,167 @@ def is_less_than(
             return GulfOfMexicoBoolean(False)
 
 
+BinaryOperation: TypeAlias = Callable[
+    [GulfOfMexicoValue, GulfOfMexicoValue, OperatorType], GulfOfMexicoValue
+]
+
+# (left type, operator, right type) -> implementation. GulfOfMexicoValue is a
+# wildcard matching any type; the most specific registration wins.
+BINARY_OPERATIONS: dict[tuple[type, OperatorType, type], BinaryOperation] = {}
+
+
+def binary_operation(left_type: type, right_type: type, *operators: OperatorType):
+    def register(func: BinaryOperation) -> BinaryOperation:
+        for operator in operators:
+            BINARY_OPERATIONS[(left_type, operator, right_type)] = func
+        return func
+
+    return register
+
+
+def lookup_binary_operation(
+    left: GulfOfMexicoValue, operator: OperatorType, right: GulfOfMexicoValue
+) -> Optional[BinaryOperation]:
+    for key in [
+        (type(left), operator, type(right)),
+        (type(left), operator, GulfOfMexicoValue),
+        (GulfOfMexicoValue, operator, type(right)),
+        (GulfOfMexicoValue, operator, GulfOfMexicoValue),
+    ]:
+        if key in BINARY_OPERATIONS:
+            return BINARY_OPERATIONS[key]
+    return None
+
+
+@binary_operation(GulfOfMexicoString, GulfOfMexicoValue, OperatorType.ADD)
+@binary_operation(GulfOfMexicoValue, GulfOfMexicoString, OperatorType.ADD)
+def concatenate_strings(
+    left: GulfOfMexicoValue, right: GulfOfMexicoValue, _: OperatorType
+) -> GulfOfMexicoString:
+    return GulfOfMexicoString(db_to_string(left).value + db_to_string(right).value)
+
+
+@binary_operation(GulfOfMexicoList, GulfOfMexicoList, OperatorType.ADD)
+def concatenate_lists(
+    left: GulfOfMexicoList, right: GulfOfMexicoList, _: OperatorType
+) -> GulfOfMexicoList:
+    return GulfOfMexicoList(left.values + right.values)
+
+
+@binary_operation(
+    GulfOfMexicoValue,
+    GulfOfMexicoValue,
+    OperatorType.ADD,
+    OperatorType.SUB,
+    OperatorType.MUL,
+    OperatorType.DIV,
+    OperatorType.EXP,
+)
+def perform_arithmetic(
+    left: GulfOfMexicoValue, right: GulfOfMexicoValue, operator: OperatorType
+) -> GulfOfMexicoValue:
+    left_num = db_to_number(left)
+    right_num = db_to_number(right)
+    if (
+        operator == OperatorType.DIV and abs(right_num.value) < FLOAT_TO_INT_PREC
+    ):  # pretty much zero
+        return GulfOfMexicoUndefined()
+    elif (
+        operator == OperatorType.EXP
+        and left_num.value < -FLOAT_TO_INT_PREC
+        and not is_int(right_num.value)
+    ):
+        raise_error_at_line(
+            filename,
+            code,
+            current_line,
+            "Cannot raise a negative base to a non-integer exponent.",
+        )
+    match operator:
+        case OperatorType.ADD:
+            result = left_num.value + right_num.value
+        case OperatorType.SUB:
+            result = left_num.value - right_num.value
+        case OperatorType.MUL:
+            result = left_num.value * right_num.value
+        case OperatorType.DIV:
+            result = left_num.value / right_num.value
+        case OperatorType.EXP:
+            result = pow(left_num.value, right_num.value)
+    return GulfOfMexicoNumber(result)
+
+
+@binary_operation(
+    GulfOfMexicoValue,
+    GulfOfMexicoValue,
+    OperatorType.LT,
+    OperatorType.GT,
+    OperatorType.LE,
+    OperatorType.GE,
+)
+def perform_comparison(
+    left: GulfOfMexicoValue, right: GulfOfMexicoValue, operator: OperatorType
+) -> GulfOfMexicoBoolean:
+    match operator:
+        case OperatorType.LT:
+            return is_less_than(left, right)
+        case OperatorType.GE:
+            return db_not(is_less_than(left, right))
+    is_eq = is_really_equal(left, right)
+    is_less = is_less_than(left, right)
+    is_le = False
+    match is_eq.value, is_less.value:  # performs the OR operation
+        case (True, _) | (_, True):
+            is_le = True
+        case (None, _) | (_, None):
+            is_le = None
+    if operator == OperatorType.LE:
+        return GulfOfMexicoBoolean(is_le)
+    return db_not(GulfOfMexicoBoolean(is_le))
+
+
+@binary_operation(GulfOfMexicoValue, GulfOfMexicoMap, OperatorType.IN)
+def map_has_key(
+    left: GulfOfMexicoValue, right: GulfOfMexicoMap, _: OperatorType
+) -> GulfOfMexicoBoolean:
+    if not isinstance(left, (GulfOfMexicoString, GulfOfMexicoNumber)):
+        return GulfOfMexicoBoolean(False)
+    return GulfOfMexicoBoolean(left.value in right.self_dict)
+
+
+@binary_operation(GulfOfMexicoValue, GulfOfMexicoList, OperatorType.IN)
+def list_contains(
+    left: GulfOfMexicoValue, right: GulfOfMexicoList, _: OperatorType
+) -> GulfOfMexicoBoolean:
+    return GulfOfMexicoBoolean(any(is_equal(left, v).value for v in right.values))
+
+
+@binary_operation(GulfOfMexicoValue, GulfOfMexicoString, OperatorType.IN)
+def string_contains(
+    left: GulfOfMexicoValue, right: GulfOfMexicoString, _: OperatorType
+) -> GulfOfMexicoBoolean:
+    return GulfOfMexicoBoolean(db_to_string(left).value in right.value)
+
+
+@binary_operation(GulfOfMexicoValue, GulfOfMexicoValue, OperatorType.IN)
+def membership_unsupported(
+    left: GulfOfMexicoValue, right: GulfOfMexicoValue, _: OperatorType
+) -> GulfOfMexicoBoolean:
+    raise NonFormattedError(
+        f"Cannot check membership in {type(right).__name__}; use a map, list or string."
+    )
+
+
 def perform_two_value_operation(
     left: GulfOfMexicoValue,
     right: GulfOfMexicoValue,
     operator: OperatorType,
     operator_token: Token,
 ) -> GulfOfMexicoValue:
+    if operation := lookup_binary_operation(left, operator, right):
+        return operation(left, right, operator)
+
     match operator:
-        case OperatorType.ADD:
-            if isinstance(left, GulfOfMexicoString) or isinstance(
-                right, GulfOfMexicoString
-            ):
-                return GulfOfMexicoString(
-                    db_to_string(left).value + db_to_string(right).value
-                )
-            left_num = db_to_number(left)
-            right_num = db_to_number(right)
-            return GulfOfMexicoNumber(left_num.value + right_num.value)
-        case OperatorType.SUB | OperatorType.MUL | OperatorType.DIV | OperatorType.EXP:
-            left_num = db_to_number(left)
-            right_num = db_to_number(right)
-            if (
-                operator == OperatorType.DIV
-                and abs(right_num.value) < FLOAT_TO_INT_PREC
-            ):  # pretty much zero
-                return GulfOfMexicoUndefined()
-            elif (
-                operator == OperatorType.EXP
-                and left_num.value < -FLOAT_TO_INT_PREC
-                and not is_int(right_num.value)
-            ):
-                raise_error_at_line(
-                    filename,
-                    code,
-                    current_line,
-                    "Cannot raise a negative base to a non-integer exponent.",
-                )
-            match operator:
-                case OperatorType.SUB:
-                    result = left_num.value - right_num.value
-                case OperatorType.MUL:
-                    result = left_num.value * right_num.value
-                case OperatorType.DIV:
-                    result = left_num.value / right_num.value
-                case OperatorType.EXP:
-                    result = pow(left_num.value, right_num.value)
-            return GulfOfMexicoNumber(result)
         case OperatorType.OR:
             left_bool = db_to_boolean(left)
             right_bool = db_to_boolean(right)
@@ -1244,22 +1529,6 @@ def perform_two_value_operation(
             if operator == OperatorType.EEEE:
                 return is_really_really_equal(left, right)
             return db_not(is_really_really_equal(left, right))
-        case OperatorType.GT | OperatorType.LE:
-            is_eq = is_really_equal(left, right)
-            is_less = is_less_than(left, right)
-            is_le = False
-            match is_eq.value, is_less.value:  # performs the OR operation
-                case (True, _) | (_, True):
-                    is_le = True
-                case (None, _) | (_, None):
-                    is_le = None
-            if operator == OperatorType.LE:
-                return GulfOfMexicoBoolean(is_le)
-            return db_not(GulfOfMexicoBoolean(is_le))
-        case OperatorType.LT | OperatorType.GE:
-            if operator == OperatorType.LT:
-                return is_less_than(left, right)
-            return db_not(is_less_than(left, right))
 
     raise_error_at_token(filename, code, "Something went wrong here.", operator_token)
 
@@ -1283,7 +1552,13 @@ def print_expression_debug(
     expr: Union[list[Token], ExpressionTreeNode],
     value: GulfOfMexicoValue,
     namespaces: list[Namespace],
+    fallback_token: Optional[Token] = None,
 ) -> None:
+    """Print the ? debug output for an expression, and check it under --assert.
+
+    fallback_token is pointed at when the expression has no place in the
+    source, like the empty value of a bare return?.
+    """
     expr = get_built_expression(expr)
     msg = None
     match debug:
@@ -1298,26 +1573,45 @@ def print_expression_debug(
             names = gather_names_or_values(expr)
             msg = f"Expression evaluates to value {db_to_string(value).value}.\nThe value of each name in the expression is the following: \n{chr(10).join([f'  {name}: {db_to_string(get_value_from_namespaces(name, namespaces)).value}' for name in names])}\nThe expression used to get this value is: \n{expr.to_string()}"
 
-    if not msg:
-        return
-    if t := get_expr_first_token(expr):
+    t = get_expr_first_token(expr)
+    if t is None or not t.line:
+        t = fallback_token
+    if msg and t:
         debug_print(filename, code, msg, t)
-    else:
+    elif msg:
         debug_print_no_token(filename, msg)
 
+    # with --assert, a ? also checks that the value is true
+    if assert_on_debug and debug and db_to_boolean(value).value is not True:
+        message = f"Assertion failed: expression is {db_to_string(value).value}."
+        if t:
+            raise_error_at_token(filename, code, message, t)
+        raise_error_at_line(filename, code, current_line, message)
+
 
 def interpret_formatted_string(
     string_token: Token,
     namespaces: list[Namespace],
     async_statements: AsyncStatements,
     when_statement_watchers: WhenStatementWatchers,
+    escapes: bool = True,
 ) -> GulfOfMexicoString:
-    """Interpret a formatted string with ${} expressions."""
+    """Interpret a formatted string with ${} expressions.
+
+    Escape sequences are decoded in the literal parts only, so a value
+    interpolated with ${} is inserted exactly as it is.
+    """
+    decode = evaluate_escape_sequences if escapes else lambda text: text
     string_value = string_token.value
-    result = ""
+    result, literal = "", ""
     i = 0
     while i < len(string_value):
-        if string_value[i : i + 2] == "${":
+        if string_value[i] == "\\":  # keep escapes whole so \${ never interpolates
+            literal += string_value[i : i + 2]
+            i += 2
+        elif string_value[i : i + 2] == "${":
+            result += decode(literal)
+            literal = ""
             # Find the closing }
             j = i + 2
             brace_count = 1
@@ -1348,48 +1642,217 @@ def interpret_formatted_string(
             result += db_to_string(value).value
             i = j
         else:
-            result += string_value[i]
+            literal += string_value[i]
             i += 1
-    return GulfOfMexicoString(result)
+    return GulfOfMexicoString(result + decode(literal))
 
 
-def evaluate_expression(
-    expr: Union[list[Token], ExpressionTreeNode],
-    namespaces: list[dict[str, Union[Variable, Name]]],
+def evaluate_operator_chain(
+    expr: ExpressionNode,
+    namespaces: list[Namespace],
     async_statements: AsyncStatements,
     when_statement_watchers: WhenStatementWatchers,
-    *,
-    ignore_string_escape_sequences: bool = False,
 ) -> GulfOfMexicoValue:
-    """Wrapper for the evaluate_expression_for_real function that checks deleted values on each run."""
-    retval = evaluate_expression_for_real(
-        expr,
+    """Evaluate a binary operation and any operations nested down its left side.
+
+    Long chains like 1+1+...+1 parse into trees that lean left, so the left
+    side is walked with a loop instead of recursion, then folded back up.
+    """
+    chain = [expr]
+    while isinstance(chain[-1].left, ExpressionNode):
+        chain.append(chain[-1].left)
+    left = evaluate_expression(
+        chain[-1].left, namespaces, async_statements, when_statement_watchers
+    )
+    for node in reversed(chain):
+        if (
+            db_to_boolean(left).value == True and node.operator == OperatorType.OR
+        ):  # handle short curcuiting for True or __
+            continue
+        elif (
+            db_to_boolean(left).value == False and node.operator == OperatorType.AND
+        ):  # handle short curcuiting for False and __
+            continue
+        right = evaluate_expression(
+            node.right, namespaces, async_statements, when_statement_watchers
+        )
+        overloaded = call_operator_method(
+            left,
+            right,
+            node.operator,
+            node.operator_token,
+            namespaces,
+            when_statement_watchers,
+        )
+        if overloaded is not None:
+            left = overloaded
+            check_not_deleted(left)
+            continue
+        try:
+            left = perform_two_value_operation(
+                left, right, node.operator, node.operator_token
+            )
+        except NonFormattedError as e:
+            if node is expr or not node.operator_token.line:
+                raise
+            raise_error_at_token(filename, code, str(e), node.operator_token)
+        check_not_deleted(left)
+    return left
+
+
+# methods a class can define to overload an operator, called on the left operand
+OPERATOR_METHODS = {
+    OperatorType.ADD: "__add__",
+    OperatorType.SUB: "__sub__",
+    OperatorType.MUL: "__mul__",
+    OperatorType.EE: "__eq__",
+}
+
+
+def get_object_method(
+    value: GulfOfMexicoValue, name: str
+) -> Optional[GulfOfMexicoFunction]:
+    """The method called name if value is an object whose class defines one."""
+    if not isinstance(value, GulfOfMexicoObject):
+        return None
+    entry = value.namespace.get(name)
+    if entry is None or not isinstance(entry.value, GulfOfMexicoFunction):
+        return None
+    return entry.value
+
+
+def call_object_method(
+    obj: GulfOfMexicoObject,
+    name: str,
+    args: list[GulfOfMexicoValue],
+    token: Token,
+    namespaces: list[Namespace],
+    when_statement_watchers: WhenStatementWatchers,
+) -> GulfOfMexicoValue:
+    """Call obj.name(*args), with errors pointing at token."""
+    call = FunctionNode(Token(TokenType.NAME, name, token.line, token.col), [])
+    return evaluate_normal_function(
+        call,
+        get_object_method(obj, name),  # type: ignore
+        namespaces + [obj.namespace],
+        args,
+        when_statement_watchers,
+    )
+
+
+def call_operator_method(
+    left: GulfOfMexicoValue,
+    right: GulfOfMexicoValue,
+    operator: OperatorType,
+    token: Token,
+    namespaces: list[Namespace],
+    when_statement_watchers: WhenStatementWatchers,
+) -> Optional[GulfOfMexicoValue]:
+    """left's overload of operator called with right, or None if it has none."""
+    method = OPERATOR_METHODS.get(operator)
+    if not method or not get_object_method(left, method):
+        return None
+    return call_object_method(
+        left,  # type: ignore
+        method,
+        [right],
+        token,
         namespaces,
-        async_statements,
         when_statement_watchers,
-        ignore_string_escape_sequences,
     )
+
+
+def check_not_deleted(value: GulfOfMexicoValue) -> None:
     if (
-        isinstance(retval, (GulfOfMexicoNumber, GulfOfMexicoString))
-        and retval in deleted_values
+        isinstance(value, (GulfOfMexicoNumber, GulfOfMexicoString))
+        and value in deleted_values
     ):
         raise_error_at_line(
-            filename, code, current_line, f"The value {retval.value} has been deleted."
+            filename, code, current_line, f"The value {value.value} has been deleted."
+        )
+
+
+def to_native_index(
+    value: GulfOfMexicoValue, index: GulfOfMexicoValue
+) -> GulfOfMexicoValue:
+    """Turn a 0-based list or string index into the -1 based one the value uses.
+
+    Only applies with zero_based_indexing, where negative indices count from the end.
+    """
+    if not zero_based_indexing or not isinstance(index, GulfOfMexicoNumber):
+        return index
+    if isinstance(value, GulfOfMexicoList):
+        length = len(value.values)
+    elif isinstance(value, GulfOfMexicoString):
+        length = len(value.value)
+    else:
+        return index
+    position = index.value + length if index.value < 0 else index.value
+    return GulfOfMexicoNumber(position - 1)
+
+
+def evaluate_expression(
+    expr: Union[list[Token], ExpressionTreeNode],
+    namespaces: list[dict[str, Union[Variable, Name]]],
+    async_statements: AsyncStatements,
+    when_statement_watchers: WhenStatementWatchers,
+    *,
+    ignore_string_escape_sequences: bool = False,
+) -> GulfOfMexicoValue:
+    """Wrapper for the evaluate_expression_for_real function that checks deleted values on each run.
+
+    Runtime errors from builtins and values (NonFormattedError) are re-raised with the
+    source line of the innermost expression that failed.
+    """
+    expr = get_built_expression(expr)
+    try:
+        retval = evaluate_expression_for_real(
+            expr,
+            namespaces,
+            async_statements,
+            when_statement_watchers,
+            ignore_string_escape_sequences,
         )
+    except NonFormattedError as e:
+        token = (
+            expr.operator_token
+            if isinstance(expr, ExpressionNode)
+            else get_expr_first_token(expr)
+        )
+        if token is None or not token.line:
+            raise
+        raise_error_at_token(filename, code, str(e), token)
+    check_not_deleted(retval)
     return retval
 
 
-def evaluate_escape_sequences(string_value: GulfOfMexicoString) -> GulfOfMexicoString:
-    """Process escape sequences in a GulfOfMexicoString."""
-    # Simple escape sequence processing
-    escaped = string_value.value
-    escaped = escaped.replace("\\n", "\n")
-    escaped = escaped.replace("\\t", "\t")
-    escaped = escaped.replace("\\r", "\r")
-    escaped = escaped.replace('\\"', '"')
-    escaped = escaped.replace("\\'", "'")
-    escaped = escaped.replace("\\\\", "\\")
-    return GulfOfMexicoString(escaped)
+SIMPLE_ESCAPES = {
+    "n": "\n",
+    "t": "\t",
+    "r": "\r",
+    '"': '"',
+    "'": "'",
+    "\\": "\\",
+    "$": "$",  # \${ is a literal ${, not an interpolation
+}
+
+
+def decode_escape(match: re.Match[str]) -> str:
+    escape = match.group(1)
+    if escape in SIMPLE_ESCAPES:
+        return SIMPLE_ESCAPES[escape]
+    if not escape.startswith("u"):
+        raise NonFormattedError(f"Invalid escape sequence: \\{escape}")
+    digits = escape[2:-1] if escape.startswith("u{") else escape[1:]
+    code_point = int(digits, 16) if re.fullmatch(r"[0-9a-fA-F]{1,6}", digits) else -1
+    if not 0 <= code_point <= 0x10FFFF or 0xD800 <= code_point <= 0xDFFF:
+        raise NonFormattedError(f"Invalid unicode escape: \\{escape}")
+    return chr(code_point)
+
+
+def evaluate_escape_sequences(string_value: str) -> str:
+    """Process escape sequences in the text of a string literal, including \\u{hex}."""
+    return ESCAPE_PATTERN.sub(decode_escape, string_value)
 
 
 def evaluate_expression_for_real(
@@ -1410,7 +1873,12 @@ def evaluate_expression_for_real(
             # make sure it exists and it is actually a function in the namespace
             if func is None:
                 raise_error_at_token(
-                    filename, code, "Cannot find token in namespace.", expr.name
+                    filename,
+                    code,
+                    "Cannot find token in namespace.",
+                    expr.name,
+                    UndefinedNameError,
+                    name=expr.name.value,
                 )
 
             # check the thing in the await symbol. if awaiting a single function that is async, evaluate it as not async
@@ -1442,6 +1910,8 @@ def evaluate_expression_for_real(
                             code,
                             "Cannot find token in namespaces.",
                             expr.name,
+                            UndefinedNameError,
+                            name=expr.name.value,
                         )
 
                 elif func.value.value == "previous":
@@ -1652,15 +2122,13 @@ def evaluate_expression_for_real(
 
         case ValueNode():  # done :)
             if expr.name_or_value.type == TokenType.STRING:
-                retval = interpret_formatted_string(
+                return interpret_formatted_string(
                     expr.name_or_value,
                     namespaces,
                     async_statements,
                     when_statement_watchers,
+                    escapes=not ignore_string_escape_sequences,
                 )
-                if not ignore_string_escape_sequences:
-                    return evaluate_escape_sequences(retval)
-                return retval
             return get_value_from_namespaces(expr.name_or_value, namespaces)
 
         case IndexNode():  # done :)
@@ -1670,6 +2138,18 @@ def evaluate_expression_for_real(
             index = evaluate_expression(
                 expr.index, namespaces, async_statements, when_statement_watchers
             )
+            if get_object_method(value, "__index__"):
+                token = get_expr_first_token(expr) or Token(
+                    TokenType.NAME, "", current_line, 0
+                )
+                return call_object_method(
+                    value,  # type: ignore
+                    "__index__",
+                    [index],
+                    token,
+                    namespaces,
+                    when_statement_watchers,
+                )
             if not isinstance(value, GulfOfMexicoIndexable):
                 raise_error_at_line(
                     filename,
@@ -1677,25 +2157,11 @@ def evaluate_expression_for_real(
                     current_line,
                     "Attempting to index a value that is not indexable.",
                 )
-            return value.access_index(index)
+            return value.access_index(to_native_index(value, index))
 
         case ExpressionNode():  # done :)
-            left = evaluate_expression(
-                expr.left, namespaces, async_statements, when_statement_watchers
-            )
-            if (
-                db_to_boolean(left).value == True and expr.operator == OperatorType.OR
-            ):  # handle short curcuiting for True or __
-                return left
-            elif (
-                db_to_boolean(left).value == False and expr.operator == OperatorType.AND
-            ):  # handle short curcuiting for False and __
-                return left
-            right = evaluate_expression(
-                expr.right, namespaces, async_statements, when_statement_watchers
-            )
-            return perform_two_value_operation(
-                left, right, expr.operator, expr.operator_token
+            return evaluate_operator_chain(
+                expr, namespaces, async_statements, when_statement_watchers
             )
 
         case SingleOperatorNode():
@@ -1936,12 +2402,15 @@ def determine_statement_type(
 ) -> Optional[CodeStatement]:
     instance_to_keywords: dict[type[CodeStatementKeywordable], set[str]] = {
         Conditional: {"if"},
+        MatchStatement: {"match"},
+        RepeatStatement: {"repeat"},
         WhenStatement: {"when"},
         AfterStatement: {"after"},
         ClassDeclaration: {"class", "className"},
         DeleteStatement: {"delete"},
         ReverseStatement: {"reverse"},
         ImportStatement: {"import"},
+        DebugStatement: {"debug"},
     }
 
     for st in possible_statements:
@@ -2221,11 +2690,18 @@ def interpret_name_watching_statement(
             )
         case Conditional():
             execute_conditional(
-                expr_val, statement.code, namespaces, when_statement_watchers, {}, []
+                expr_val,
+                statement.code,
+                namespaces,
+                when_statement_watchers,
+                {},
+                [],
+                maybe_statements=statement.maybe_code,
+                else_statements=statement.else_code,
             )
         case AfterStatement():
             execute_after_statement(
-                expr_val, statement.code, namespaces, when_statement_watchers
+                expr_val, statement.code, namespaces, when_statement_watchers, {}, []
             )
         case ExpressionStatement():
             print_expression_debug(
@@ -2251,16 +2727,19 @@ def execute_conditional(
     when_statement_watchers: WhenStatementWatchers,
     importable_names: dict[str, dict[str, GulfOfMexicoValue]],
     exported_names: list[tuple[str, str, GulfOfMexicoValue]],
+    maybe_statements: Optional[list[tuple[CodeStatement, ...]]] = None,
+    else_statements: Optional[list[tuple[CodeStatement, ...]]] = None,
 ) -> Optional[GulfOfMexicoValue]:
     condition = db_to_boolean(condition)
-    execute = (
-        condition.value == True
-        if condition.value is not None
-        else random.random() < 0.50
-    )
-    if execute:
+    if condition.value is None and maybe_statements is not None:
+        branch = maybe_statements
+    elif condition.value is None:
+        branch = statements_inside_scope if random.random() < 0.50 else else_statements
+    else:
+        branch = statements_inside_scope if condition.value else else_statements
+    if branch is not None:
         return interpret_code_statements(
-            statements_inside_scope,
+            branch,
             namespaces + [{}],
             [],
             when_statement_watchers + [{}],
@@ -2269,9 +2748,88 @@ def execute_conditional(
         )  # empty scope and async statements, just for this :)
 
 
+# run the first arm whose pattern == value (or the _ arm) inside a new scope
+def execute_match(
+    value: GulfOfMexicoValue,
+    statement: MatchStatement,
+    namespaces: list[Namespace],
+    async_statements: AsyncStatements,
+    when_statement_watchers: WhenStatementWatchers,
+    importable_names: dict[str, dict[str, GulfOfMexicoValue]],
+    exported_names: list[tuple[str, str, GulfOfMexicoValue]],
+) -> Optional[GulfOfMexicoValue]:
+    branch = statement.default_code
+    for pattern, code in statement.arms:
+        pattern_value = evaluate_expression(
+            pattern, namespaces, async_statements, when_statement_watchers
+        )
+        # a class's __eq__ decides, the same as for ==
+        matched = call_operator_method(
+            value,
+            pattern_value,
+            OperatorType.EE,
+            statement.keyword,
+            namespaces,
+            when_statement_watchers,
+        )
+        if matched is None:
+            matched = is_equal(value, pattern_value)
+        if db_to_boolean(matched).value == True:
+            branch = code
+            break
+    if branch is not None:
+        return interpret_code_statements(
+            branch,
+            namespaces + [{}],
+            [],
+            when_statement_watchers + [{}],
+            importable_names,
+            exported_names,
+        )
+
+
+# run the body count times, each time in a fresh scope; every pass is charged
+# against the statement budget so an empty body can't spin forever
+def execute_repeat(
+    count: GulfOfMexicoValue,
+    statement: RepeatStatement,
+    namespaces: list[Namespace],
+    when_statement_watchers: WhenStatementWatchers,
+    importable_names: dict[str, dict[str, GulfOfMexicoValue]],
+    exported_names: list[tuple[str, str, GulfOfMexicoValue]],
+) -> Optional[GulfOfMexicoValue]:
+    global max_statements
+    try:
+        times = math.floor(db_to_number(count).value)
+    except (NonFormattedError, ValueError, OverflowError):
+        raise_error_at_token(
+            filename,
+            code,
+            "The count of a repeat statement must be a finite number.",
+            statement.keyword,
+        )
+    result = None
+    for _ in range(times):
+        if max_statements is not None:
+            if max_statements <= 0:
+                raise_error_at_line(
+                    filename, code, current_line, "Statement budget exceeded."
+                )
+            max_statements -= 1
+        result = interpret_code_statements(
+            statement.code,
+            namespaces + [{}],
+            [],
+            when_statement_watchers + [{}],
+            importable_names,
+            exported_names,
+        )
+    return result
+
+
 # this is the equaivalent of an event listener
 def get_mouse_event_object(
-    x: int, y: int, button: mouse.Button, event: str
+    x: int, y: int, button: Union[mouse.Button, str], event: str
 ) -> GulfOfMexicoObject:
     return GulfOfMexicoObject(
         "MouseEvent",
@@ -2284,183 +2842,214 @@ def get_mouse_event_object(
     )
 
 
-def get_keyboard_event_object(
-    key: Optional[Union[keyboard.Key, keyboard.KeyCode]], event: str
-) -> GulfOfMexicoObject:
+def get_keyboard_event_object(key: str, event: str) -> GulfOfMexicoObject:
     return GulfOfMexicoObject(
         "KeyboardEvent",
         {
-            "key": Name("key", GulfOfMexicoString(str(key).split(".")[-1])),
+            "key": Name("key", GulfOfMexicoString(key)),
             "event": Name("event", GulfOfMexicoString(event)),
         },
     )
 
 
-def execute_after_statement(
-    event: GulfOfMexicoValue,
-    statements_inside_scope: list[tuple[CodeStatement, ...]],
-    namespaces: list[Namespace],
-    when_statement_watchers: WhenStatementWatchers,
-    importable_names: dict[str, dict[str, GulfOfMexicoValue]],
-    exported_names: list[tuple[str, str, GulfOfMexicoValue]],
-) -> None:
+@dataclass
+class InputEvent:
+    """A raw mouse or keyboard event, either from pynput or injected with inject_events()."""
 
-    if not KEY_MOUSE_IMPORTED:
-        raise_error_at_line(
-            filename,
-            code,
-            current_line,
-            "Attempted to use mouse and keyboard functionality without importing the [input] extra dependency.",
-        )
+    device: Literal["mouse", "key"]
+    pressed: bool
+    key: str = ""  # keyboard only
+    button: str = ""  # mouse only
+    x: int = 0
+    y: int = 0
 
-    if not isinstance(event, GulfOfMexicoString):
-        raise_error_at_line(
-            filename,
-            code,
-            current_line,
-            f'Invalid event for the "after" statement: "{db_to_string(event)}"',
+
+INPUT_EVENTS = {"mouseclick", "mousedown", "mouseup", "keyclick", "keydown", "keyup"}
+
+
+@dataclass
+class AfterEventHandler:
+    """The body of an `after "<event>"` statement, waiting for matching input events."""
+
+    event: str
+    statements_inside_scope: list[tuple[CodeStatement, ...]]
+    namespaces: list[Namespace]
+    when_statement_watchers: WhenStatementWatchers
+    importable_names: dict[str, dict[str, GulfOfMexicoValue]]
+    exported_names: list[tuple[str, str, GulfOfMexicoValue]]
+    held: set[str] = field(default_factory=set)  # buttons/keys pressed for *click
+
+    def handle(self, input_event: InputEvent) -> None:
+        if not self.event.startswith(input_event.device):
+            return
+        held_name = input_event.button or input_event.key
+        if self.event.endswith("click"):
+            if input_event.pressed:
+                self.held.add(held_name)
+                return
+            if held_name not in self.held:
+                return
+            self.held.discard(held_name)
+        elif self.event.endswith("down") != input_event.pressed:
+            return
+
+        if input_event.device == "mouse":
+            event_object = get_mouse_event_object(
+                input_event.x, input_event.y, input_event.button, self.event
+            )
+        else:
+            event_object = get_keyboard_event_object(input_event.key, self.event)
+        interpret_code_statements(
+            self.statements_inside_scope,
+            self.namespaces + [{"event": Name("event", event_object)}],
+            [],
+            self.when_statement_watchers + [{}],
+            self.importable_names,
+            self.exported_names,
         )
 
-    match event.value:
-        case "mouseclick":
-            mouse_buttons = {}
 
-            def listener_func(x: int, y: int, button: mouse.Button, pressed: bool):
-                nonlocal namespaces, statements_inside_scope
-                if pressed:
-                    mouse_buttons[button] = (x, y)
-                else:
-                    if mouse_buttons[
-                        button
-                    ]:  # it has been released and then pressed again
-                        interpret_code_statements(
-                            statements_inside_scope,
-                            namespaces
-                            + [
-                                {
-                                    "event": Name(
-                                        "event",
-                                        get_mouse_event_object(
-                                            x, y, button, event.value
-                                        ),
-                                    )
-                                }
-                            ],
-                            [],
-                            when_statement_watchers + [{}],
-                            importable_names,
-                            exported_names,
-                        )
-                    del mouse_buttons[button]
-
-            listener = mouse.Listener(on_click=listener_func)  # type: ignore
-
-        case "mousedown":
-
-            def listener_func(x: int, y: int, button: mouse.Button, pressed: bool):
-                nonlocal namespaces, statements_inside_scope
-                if pressed:
-                    interpret_code_statements(
-                        statements_inside_scope,
-                        namespaces
-                        + [
-                            {
-                                "event": Name(
-                                    "event",
-                                    get_mouse_event_object(x, y, button, event.value),
-                                )
-                            }
-                        ],
-                        [],
-                        when_statement_watchers + [{}],
-                        importable_names,
-                        exported_names,
-                    )
+def process_input_event(input_event: InputEvent) -> None:
+    """Dispatch one input event to every registered after statement."""
+    for handler in list(after_event_handlers):
+        handler.handle(input_event)
 
-            listener = mouse.Listener(on_click=listener_func)  # type: ignore
-
-        case "mouseup":
-
-            def listener_func(x: int, y: int, button: mouse.Button, pressed: bool):
-                nonlocal namespaces, statements_inside_scope
-                if not pressed:
-                    interpret_code_statements(
-                        statements_inside_scope,
-                        namespaces
-                        + [
-                            {
-                                "event": Name(
-                                    "event",
-                                    get_mouse_event_object(x, y, button, event.value),
-                                )
-                            }
-                        ],
-                        [],
-                        when_statement_watchers + [{}],
-                        importable_names,
-                        exported_names,
-                    )
 
-            listener = mouse.Listener(on_click=listener_func)  # type: ignore
+def inject_events(input_events: list[InputEvent]) -> None:
+    """Synchronously process a sequence of input events, bypassing pynput (for tests)."""
+    for input_event in input_events:
+        process_input_event(input_event)
 
-        case "keyclick":
-            keys = set()
 
-            def on_press(key: Optional[Union[keyboard.Key, keyboard.KeyCode]]):
-                nonlocal namespaces, statements_inside_scope
-                keys.add(key)
+def input_listeners_available() -> bool:
+    """Whether the pynput listeners are running (False before the first after statement)."""
+    return input_listener_state is True
 
-            def on_release(key: Optional[Union[keyboard.Key, keyboard.KeyCode]]):
-                nonlocal namespaces, statements_inside_scope
-                if key in keys:
-                    event_object = get_keyboard_event_object(key.char if isinstance(key, keyboard.KeyCode) else key, event.value)  # type: ignore
-                    interpret_code_statements(statements_inside_scope, namespaces + [{"event": Name("event", event_object)}], [], when_statement_watchers + [{}], importable_names, exported_names)  # type: ignore
-                keys.discard(key)
 
-            listener = keyboard.Listener(on_press=on_press, on_release=on_release)  # type: ignore
+def start_input_listeners() -> None:
+    """Start the pynput mouse and keyboard listeners the first time they are needed.
 
-        case "keydown":
+    If they cannot be started (e.g. no display or no input permissions), a single
+    message is printed and after statements simply never receive system events.
+    """
+    global input_listener_state
+    if input_listener_state is not None:
+        return
 
-            def on_press(key: Optional[Union[keyboard.Key, keyboard.KeyCode]]):
-                nonlocal namespaces, statements_inside_scope
-                event_object = get_keyboard_event_object(key.char if isinstance(key, keyboard.KeyCode) else key, event.value)  # type: ignore
-                interpret_code_statements(
-                    statements_inside_scope,
-                    namespaces + [{"event": Name("event", event_object)}],
-                    [],
-                    when_statement_watchers + [{}],
-                    importable_names,
-                    exported_names,
-                )
+    def key_event(key: Optional[Union[keyboard.Key, keyboard.KeyCode]], pressed: bool):
+        # character keys (including ".") keep their char; Key.space becomes "space"
+        if isinstance(key, keyboard.KeyCode):
+            name = key.char or str(key)
+        else:
+            name = str(key).split(".")[-1]
+        process_input_event(InputEvent("key", pressed, key=name))
 
-            listener = keyboard.Listener(on_press=on_press)  # type: ignore
+    def on_click(x: int, y: int, button: mouse.Button, pressed: bool):
+        button_name = str(button).split(".")[-1]
+        process_input_event(InputEvent("mouse", pressed, button=button_name, x=x, y=y))
 
-        case "keyup":
+    try:
+        from pynput import keyboard, mouse
+
+        for listener in (
+            mouse.Listener(on_click=on_click),  # type: ignore
+            keyboard.Listener(
+                on_press=lambda key: key_event(key, True),  # type: ignore
+                on_release=lambda key: key_event(key, False),  # type: ignore
+            ),
+        ):
+            listener.start()
+            after_listeners.append(listener)
+    except Exception as e:
+        input_listener_state = False
+        print(
+            f"Input events are unavailable on this system ({e}); after statements waiting on mouse or keyboard events will not run.",
+            file=sys.stderr,
+        )
+        return
+    input_listener_state = True
 
-            def on_release(key: Optional[Union[keyboard.Key, keyboard.KeyCode]]):
-                nonlocal namespaces, statements_inside_scope
-                event_object = get_keyboard_event_object(key.char if isinstance(key, keyboard.KeyCode) else key, event.value)  # type: ignore
-                interpret_code_statements(
-                    statements_inside_scope,
-                    namespaces + [{"event": Name("event", event_object)}],
-                    [],
-                    when_statement_watchers + [{}],
-                    importable_names,
-                    exported_names,
-                )
 
-            listener = keyboard.Listener(on_release=on_release)  # type: ignore
+def run_due_after_bodies() -> None:
+    """Run the bodies of duration after statements whose delay has passed.
 
-        case _:
+    They run on the main thread between statements instead of on a timer
+    thread, so they never race with the statement being executed and their
+    errors are reported like any other.
+    """
+    now = after_clock()
+    due = sorted(
+        (entry for entry in scheduled_afters if entry[0] <= now), key=lambda e: e[0]
+    )
+    for entry in due:
+        scheduled_afters.remove(entry)
+        entry[1]()
+
+
+def parse_after_duration(event: str) -> Optional[float]:
+    """Return the delay in seconds for events like "0.5s", "250ms" or "2m", else None."""
+    match = re.fullmatch(r"\s*(\d+(?:\.\d+)?)\s*(ms|s|m)\s*", event)
+    if match is None:
+        return None
+    amount, unit = float(match.group(1)), match.group(2)
+    return amount / 1000 if unit == "ms" else amount * 60 if unit == "m" else amount
+
+
+def execute_after_statement(
+    event: GulfOfMexicoValue,
+    statements_inside_scope: list[tuple[CodeStatement, ...]],
+    namespaces: list[Namespace],
+    when_statement_watchers: WhenStatementWatchers,
+    importable_names: dict[str, dict[str, GulfOfMexicoValue]],
+    exported_names: list[tuple[str, str, GulfOfMexicoValue]],
+) -> None:
+
+    # durations like "1s" or "250ms" run the body once after the delay
+    if isinstance(event, GulfOfMexicoString) and (
+        delay := parse_after_duration(event.value)
+    ) is not None:
+
+        def run_after_delay():
+            interpret_code_statements(
+                statements_inside_scope,
+                namespaces + [{}],
+                [],
+                when_statement_watchers + [{}],
+                importable_names,
+                exported_names,
+            )
+
+        scheduled_afters.append((after_clock() + delay, run_after_delay))
+        return
+
+    if not isinstance(event, GulfOfMexicoString) or event.value not in INPUT_EVENTS:
+        raise_error_at_line(
+            filename,
+            code,
+            current_line,
+            f'Invalid event for the "after" statement: "{db_to_string(event).value}"',
+        )
+
+    if use_system_input:
+        if not KEY_MOUSE_IMPORTED:
             raise_error_at_line(
                 filename,
                 code,
                 current_line,
-                f'Invalid event for the "after" statement: "{db_to_string(event)}"',
+                "Attempted to use mouse and keyboard functionality without importing the [input] extra dependency.",
             )
+        start_input_listeners()
 
-    listener.start()
-    after_listeners.append(listener)  # pyright: ignore[reportUnknownMemberType]
+    after_event_handlers.append(
+        AfterEventHandler(
+            event.value,
+            statements_inside_scope,
+            namespaces,
+            when_statement_watchers,
+            importable_names,
+            exported_names,
+        )
+    )
 
 
 def gather_names_or_values(expr: ExpressionTreeNode) -> set[Token]:
@@ -2487,6 +3076,20 @@ def gather_names_or_values(expr: ExpressionTreeNode) -> set[Token]:
     return names
 
 
+def has_undefined_names(expr: ExpressionTreeNode, namespaces: list[Namespace]) -> bool:
+    """Whether the expression uses a name that isn't declared (yet)."""
+    for token in gather_names_or_values(expr):
+        if token.type != TokenType.NAME or get_name_from_namespaces(
+            token.value, namespaces
+        ):
+            continue
+        try:
+            determine_non_name_value(token)
+        except UndefinedNameError:
+            return True
+    return False
+
+
 def register_when_statement(
     condition: Union[list[Token], ExpressionTreeNode],
     statements_inside_scope: list[tuple[CodeStatement, ...]],
@@ -2536,24 +3139,23 @@ def register_when_statement(
     # the last comprehension watches callers of things (like list in list.length), and requires some implementation in the evaluate_expression function
     # so that the caller of a function is also observed for it being called
 
-    # register for future whens
+    # hand out a handle so the when can be stopped later with stop_when()
+    global last_when_handle
+    last_when_handle += 1
+    when_handles[last_when_handle] = built_condition
+
+    # register for future whens. the built condition, the body, and the current
+    # scope chain are stored so the watcher runs with the same scope when
+    # triggered. the namespaces themselves are shared (not copied) so the condition
+    # sees later assignments, and every name shares one watcher tuple
+    watcher = (built_condition, statements_inside_scope, list(namespaces))
     for name in dict_keys:
-        if name not in when_statement_watchers[-1]:
-            when_statement_watchers[-1][name] = []
-        # store the built condition, the body, and a snapshot of the current
-        # namespaces so the watcher runs with the same scope when triggered.
-        captured_ns = deepcopy(namespaces)
-        # DEBUG: Print what we're capturing
-        try:
-            debug_keys = [list(ns.keys()) for ns in captured_ns]
-            debug_print_no_token(
-                filename, f"Capturing namespaces for when: {debug_keys}"
-            )
-        except Exception:
-            pass
-        when_statement_watchers[-1][name].append(
-            (built_condition, statements_inside_scope, captured_ns)
-        )
+        when_statement_watchers[-1].setdefault(name, []).append(watcher)
+
+    # deferred whens only fire once one of their dependencies changes, and a
+    # condition on names that don't exist yet isn't true yet
+    if defer_when_statements or has_undefined_names(built_condition, namespaces):
+        return
 
     # check the condition now
     # Evaluate the condition immediately inside the same namespaces that the
@@ -2608,8 +3210,10 @@ def get_name_from_namespaces(
 
     Supports dotted access for namespaceable values (object/list/string namespaces).
     """
-    # Fast path for simple names
+    # Fast path for simple names; deep call stacks remember where each name was found
     if "." not in name:
+        if len(namespaces) >= CACHED_LOOKUP_DEPTH:
+            return get_deep_name(name, namespaces)
         for namespace in reversed(namespaces):
             if name in namespace:
                 return namespace[name]
@@ -2638,6 +3242,36 @@ def get_name_from_namespaces(
     return current_entry
 
 
+def get_deep_name(
+    name: str, namespaces: list[Namespace]
+) -> Optional[Union[Variable, Name]]:
+    """get_name_from_namespaces for a simple name, caching the scope it is found in.
+
+    The cached depth is used while namespaces keeps its length and the name is still
+    bound there. A name bound closer in would shadow it, so every new binding calls
+    forget_name_lookups first.
+    """
+    frames = name_depths.setdefault(name, {})
+    cached = frames.get(id(namespaces))
+    if cached and cached[0] is namespaces and cached[1] == len(namespaces):
+        entry = namespaces[cached[2]].get(name)
+        if entry is not None:
+            return entry
+    for depth in range(len(namespaces) - 1, -1, -1):
+        entry = namespaces[depth].get(name)
+        if entry is not None:
+            if len(frames) >= MAX_CACHED_FRAMES:
+                frames.clear()
+            frames[id(namespaces)] = (namespaces, len(namespaces), depth)
+            return entry
+    return None
+
+
+def forget_name_lookups(name: str) -> None:
+    """Drop the cached scopes of name. Call before binding it in any namespace."""
+    name_depths.pop(name, None)
+
+
 def get_name_and_namespace_from_namespaces(
     name: str, namespaces: list[Namespace]
 ) -> tuple[Optional[Union[Variable, Name]], Optional[Namespace]]:
@@ -2656,6 +3290,9 @@ def determine_non_name_value(name_or_value: Token) -> GulfOfMexicoValue:
         case TokenType.NAME:
             # Try to parse as number
             try:
+                # 0x1F, 0b1010 and 0o17 are whole numbers in another base
+                if name_or_value.value[:2].lower() in {"0x", "0b", "0o"}:
+                    return GulfOfMexicoNumber(int(name_or_value.value, 0))
                 # Check if it's an integer
                 if (
                     "." not in name_or_value.value
@@ -2683,6 +3320,8 @@ def determine_non_name_value(name_or_value: Token) -> GulfOfMexicoValue:
                     code,
                     f"Undefined name: {name_or_value.value}",
                     name_or_value,
+                    UndefinedNameError,
+                    name=name_or_value.value,
                 )
         case _:
             raise_error_at_token(
@@ -2706,19 +3345,161 @@ deleted_values: set[GulfOfMexicoValue] = set()
 # Global watchers for reactive programming
 name_watchers: NameWatchers = {}
 after_listeners: list = []
+after_event_handlers: list[AfterEventHandler] = []
+input_listener_state: Optional[bool] = None  # None until started, False if it failed
+
+# Bodies of duration after statements as (due time, body), run by run_due_after_bodies
+scheduled_afters: list[tuple[float, Callable[[], None]]] = []
+after_clock: Callable[[], float] = monotonic
+
+# When False, after statements only receive events passed to inject_events()
+use_system_input: bool = True
+
+# Registered when statements by handle (see last_when() / stop_when())
+when_handles: dict[int, ExpressionTreeNode] = {}
+last_when_handle: int = 0
+stopped_when_conditions: set[int] = set()  # ids of conditions of stopped whens
+
+# Variables with a lifetime that can run out, and the namespace each lives in
+expiring_variables: list[tuple[Namespace, Variable]] = []
+
+# Scope each simple name was found in, by name then id of the namespaces list, used
+# once a list is at least CACHED_LOOKUP_DEPTH long. Entries keep their list so its id
+# can't be reused, which is why each name only keeps MAX_CACHED_FRAMES of them
+CACHED_LOOKUP_DEPTH = 16
+MAX_CACHED_FRAMES = 1000
+name_depths: dict[str, dict[int, tuple[list[Namespace], int, int]]] = {}
 
 # Global flags
 is_lifetime_temporal: bool = False
 
+# Command-line arguments after the script filename, returned by args()
+script_args: list[str] = []
+
+# Cleared by --no-globals so persisted and public globals are not loaded at startup
+load_persisted_globals: bool = True
+
+# When set (see --defer-when), a when statement is not checked on registration
+defer_when_statements: bool = False
+
+# When set (interactive REPL), a debug statement waits for Enter before continuing
+pause_on_debug: bool = False
+
+# Remaining statement budget (None means unlimited), set by --max-statements
+max_statements: Optional[int] = None
+
+# User function calls in progress, innermost last: (function name, line of the call)
+call_stack: list[tuple[str, int]] = []
+
+# When set (see --show-traceback), runtime errors also list the calls that were active
+show_call_stack: bool = False
+
+# When set (see --lint), shadowing a builtin or an outer const prints a warning
+warn_on_shadowing: bool = False
+
+# When set (see --assert), an expression statement ending in ? must be true
+assert_on_debug: bool = False
+
+# When set (see --zero-based), list and string indices start at 0 instead of -1
+zero_based_indexing: bool = False
+
+
+def stop_when(handle: int) -> bool:
+    """Deregister the when statement with the given handle. Returns whether it was active."""
+    condition = when_handles.pop(handle, None)
+    if condition is None:
+        return False
+    stopped_when_conditions.add(id(condition))
+    return True
+
+
+@contextmanager
+def preserved_source_position() -> Iterator[None]:
+    """Restore filename, code and current_line after running some other source."""
+    global filename, code, current_line
+    saved = filename, code, current_line
+    try:
+        yield
+    finally:
+        filename, code, current_line = saved
+
+
+def evaluate_source(
+    source: str, namespaces: list[Namespace]
+) -> Optional[GulfOfMexicoValue]:
+    """Run source for eval() in the caller's scope and return its last value.
+
+    Errors point into the evaluated string, so filename and code are swapped out
+    while it runs. Its statements count against the statement budget, which is
+    what stops a self-evaluating program from recursing forever. Whens registered
+    inside only react to assignments made inside the same eval().
+    """
+    global filename, code
+    with preserved_source_position():
+        filename, code = f"{filename} (eval)", source
+        tokens = db_tokenize(filename, source)
+        statements = generate_syntax_tree(filename, tokens, source)
+        return interpret_code_statements(statements, namespaces, [], [{}], {}, [])
+
+
+def evaluate_file_isolated(path: str, source: str) -> Optional[GulfOfMexicoValue]:
+    """Run a file for import_value() in fresh namespaces and return its last value.
+
+    Nothing it declares or exports reaches the caller; only the value comes back.
+    """
+    from gulfofmexico import run_source
+
+    with preserved_source_position():
+        return run_source(source, filename=path)
+
+
+def execute_debug_statement(
+    statement: DebugStatement, namespaces: list[Namespace]
+) -> None:
+    """Print the current line and local names, then wait for Enter if pause_on_debug is set."""
+    local_names = [
+        f"  {name}: {db_to_string(entry.value).value}"
+        for name, entry in namespaces[-1].items()
+        if KEYWORDS.get(name) is not entry
+    ]
+    debug_print(
+        filename,
+        code,
+        f"Paused at line {statement.keyword.line}. Local names:\n"
+        + ("\n".join(local_names) or "  <none>"),
+        statement.keyword,
+    )
+    if pause_on_debug:
+        input("Press Enter to continue...")
+
 
 def exit_on_dead_listener() -> None:
-    """Exit if there are no active listeners remaining."""
-    if not after_listeners:
+    """Exit if there are no active listeners remaining, else run due after bodies."""
+    if not after_listeners and not scheduled_afters:
         exit()
+    run_due_after_bodies()
+
+
+# export x to "-"! prints x as a declaration instead of sending it to a file
+EXPORT_TO_STDOUT = "-"
+
+
+def export_value(
+    target: str,
+    name: str,
+    value: GulfOfMexicoValue,
+    exported_names: list[tuple[str, str, GulfOfMexicoValue]],
+) -> None:
+    if target != EXPORT_TO_STDOUT:
+        exported_names.append((target, name, value))
+    elif (source := value_to_source(value)) is None:
+        print(f"// {name}: value cannot be written as source")
+    else:
+        print(f"const const {name} = {source}!")
 
 
 def interpret_code_statements_main_wrapper(
-    statements: list[tuple[CodeStatement, ...]],
+    statements: Iterable[tuple[CodeStatement, ...]],
     namespaces: list[Namespace],
     async_statements: AsyncStatements,
     when_statement_watchers: WhenStatementWatchers,
@@ -2726,6 +3507,7 @@ def interpret_code_statements_main_wrapper(
     exported_names: list[tuple[str, str, GulfOfMexicoValue]],
 ) -> Optional[GulfOfMexicoValue]:
     """Main wrapper for interpreting code statements."""
+    name_depths.clear()  # names may have been bound from outside since the last run
     return interpret_code_statements(
         statements,
         namespaces,
@@ -2737,29 +3519,50 @@ def interpret_code_statements_main_wrapper(
 
 
 def interpret_code_statements(
-    statements: list[tuple[CodeStatement, ...]],
+    statements: Iterable[tuple[CodeStatement, ...]],
     namespaces: list[Namespace],
     async_statements: AsyncStatements,
     when_statement_watchers: WhenStatementWatchers,
     importable_names: dict[str, dict[str, GulfOfMexicoValue]],
     exported_names: list[tuple[str, str, GulfOfMexicoValue]],
 ) -> Optional[GulfOfMexicoValue]:
-    """Interpret a list of code statements."""
+    """Interpret code statements in order, pulling each one as it is reached."""
     result = None
 
     # Process each statement
     for statement_tuple in statements:
+        if scheduled_afters:
+            run_due_after_bodies()
+
         # Determine the actual statement type
         statement = determine_statement_type(statement_tuple, namespaces)
         if statement is None:
             continue
 
         # Update current line for error reporting
-        global current_line
+        global current_line, max_statements
         if hasattr(statement, "name") and hasattr(statement.name, "line"):
             current_line = statement.name.line
         elif hasattr(statement, "keyword") and hasattr(statement.keyword, "line"):
             current_line = statement.keyword.line
+        elif isinstance(statement, ExpressionStatement) and (
+            t := (
+                next(iter(statement.expression), None)  # `}!` leaves no tokens
+                if isinstance(statement.expression, list)
+                else get_expr_first_token(statement.expression)
+            )
+        ):
+            current_line = t.line
+        if expiring_variables:
+            cleanup_expired_variables()
+
+        # Enforce the statement budget so runaway programs terminate
+        if max_statements is not None:
+            if max_statements <= 0:
+                raise_error_at_line(
+                    filename, code, current_line, "Statement budget exceeded."
+                )
+            max_statements -= 1
 
         # Execute the statement based on its type
         match statement:
@@ -2770,6 +3573,9 @@ def interpret_code_statements(
                     async_statements,
                     when_statement_watchers,
                 )
+                if isinstance(result, BuiltinFunction) and result.function is db_print:
+                    # a bare print! prints an empty line, the same as print()!
+                    result = db_print() or GulfOfMexicoUndefined()
                 print_expression_debug(
                     statement.debug,
                     statement.expression,
@@ -2829,6 +3635,7 @@ def interpret_code_statements(
                     statement.expression,
                     result,
                     namespaces,
+                    fallback_token=statement.keyword,
                 )
                 return result  # Return immediately
 
@@ -2846,6 +3653,41 @@ def interpret_code_statements(
                     when_statement_watchers,
                     importable_names,
                     exported_names,
+                    maybe_statements=statement.maybe_code,
+                    else_statements=statement.else_code,
+                )
+
+            case RepeatStatement():
+                count = evaluate_expression(
+                    statement.expression,
+                    namespaces,
+                    async_statements,
+                    when_statement_watchers,
+                )
+                result = execute_repeat(
+                    count,
+                    statement,
+                    namespaces,
+                    when_statement_watchers,
+                    importable_names,
+                    exported_names,
+                )
+
+            case MatchStatement():
+                value = evaluate_expression(
+                    statement.expression,
+                    namespaces,
+                    async_statements,
+                    when_statement_watchers,
+                )
+                result = execute_match(
+                    value,
+                    statement,
+                    namespaces,
+                    async_statements,
+                    when_statement_watchers,
+                    importable_names,
+                    exported_names,
                 )
 
             case WhenStatement():
@@ -2881,8 +3723,16 @@ def interpret_code_statements(
                     [arg.value for arg in statement.args],
                     statement.code,
                     statement.is_async,
+                    (
+                        "".join(t.value for t in statement.return_type)
+                        if statement.return_type
+                        else None
+                    ),
                 )
+                if call_stack:  # defined while a function runs, so close over it
+                    func.closure = list(namespaces)
                 # Add to namespace
+                forget_name_lookups(statement.name.value)
                 namespaces[-1][statement.name.value] = Variable(
                     statement.name.value,
                     [VariableLifetime(func, 100000000000, 0, True, True)],
@@ -2910,6 +3760,7 @@ def interpret_code_statements(
                         continue
                     class_obj.namespace[k] = v
                 # Add the class to the namespace
+                forget_name_lookups(statement.name.value)
                 namespaces[-1][statement.name.value] = Name(
                     statement.name.value, class_obj
                 )
@@ -2948,13 +3799,7 @@ def interpret_code_statements(
                     reversed_str = value.value[::-1]
                     new_value = GulfOfMexicoString(reversed_str)
                     if isinstance(var, Variable):
-                        var.add_lifetime(
-                            new_value,
-                            0,  # confidence 0 for auto-generated
-                            100000000000,  # infinite duration
-                            var.can_be_reset,
-                            var.can_edit_value,
-                        )
+                        var.reassign(new_value, 0, current_line)
                     elif isinstance(var, Name):
                         var.value = new_value
                 else:
@@ -2963,14 +3808,21 @@ def interpret_code_statements(
                         code,
                         f"Cannot reverse type {type(value).__name__}. Only lists and strings can be reversed.",
                         statement.name,
+                        TypeMismatchError,
+                        expected="List or String",
+                        got=type(value).__name__,
                     )
 
+            case DebugStatement():
+                execute_debug_statement(statement, namespaces)
+
             case ImportStatement():
                 for name_token in statement.names:
                     name = name_token.value
                     found = False
                     for file_dict in importable_names.values():
                         if name in file_dict:
+                            forget_name_lookups(name)
                             namespaces[-1][name] = Name(name, file_dict[name])
                             found = True
                             break
@@ -2980,8 +3832,27 @@ def interpret_code_statements(
                             code,
                             f"Cannot find imported name: {name}",
                             name_token,
+                            UndefinedNameError,
+                            name=name,
                         )
 
+            case ExportStatement() if statement.target_file.value == filename:
+                raise_error_at_token(
+                    filename,
+                    code,
+                    f"Cannot export to {filename}: it is the file doing the exporting.",
+                    statement.target_file,
+                )
+
+            case ExportStatement() if statement.export_all:
+                target = statement.target_file.value
+                in_scope: dict[str, Union[Variable, Name]] = {}
+                for ns in namespaces:
+                    in_scope |= ns
+                for name, v in in_scope.items():
+                    if KEYWORDS.get(name) is not v:
+                        export_value(target, name, v.value, exported_names)
+
             case ExportStatement():
                 for name_token in statement.names:
                     name = name_token.value
@@ -2992,10 +3863,12 @@ def interpret_code_statements(
                             code,
                             f"Cannot export undefined name: {name}",
                             name_token,
+                            UndefinedNameError,
+                            name=name,
                         )
                     value = v.value if isinstance(v, Name) else v.value
                     target = statement.target_file.value
-                    exported_names.append((target, name, value))
+                    export_value(target, name, value, exported_names)
 
     # Process async statements
     while async_statements:
@@ -18,6 +18,9 @@ Expression Building:
     evaluated by evaluate_expression() in interpreter.py.
 
 Operator Precedence (highest to lowest):
+    The parser groups by whitespace, not by this list: the operator with the
+    most space around it splits the expression, so 1+2 * 3 is (1 + 2) * 3, and
+    equally spaced operators apply left to right, so 1+2*3 is 9.
     1. Indexing: []
     2. Function calls: ()
     3. Exponentiation: ^
@@ -25,15 +28,17 @@ Operator Precedence (highest to lowest):
     5. Multiplication/Division: *, /
     6. Addition/Subtraction: +, -
     7. Comparison: <, >, <=, >=
-    8. Equality: =, ==, ===, ====
+    8. Equality: =, ==, ===, ====, ;=, ;==, ;===
     9. Logical AND: &
     10. Logical OR: |
+    11. Argument separator: ,
 
 Special Features:
     - Short-circuit evaluation for & and |
     - String interpolation: "${expression}" within strings
     - Approximate equality with fuzzy matching
     - Multiple equality levels (=, ==, ===, ====)
+    - Confidence markers on sub-values: (5!) + 3
 """
 
 from __future__ import annotations
@@ -41,12 +46,14 @@ from abc import ABCMeta, abstractmethod
 from typing import Optional
 
 from gulfofmexico.base import (
+    ESCAPE_PATTERN,
     STR_TO_OPERATOR,
     NonFormattedError,
     Token,
     TokenType,
     OperatorType,
     InterpretationError,
+    ParseError,
     raise_error_at_token,
 )
 
@@ -54,6 +61,8 @@ from gulfofmexico.base import (
 class ExpressionTreeNode(metaclass=ABCMeta):
     """Base class for all expression tree nodes."""
 
+    confidence: int = 0  # number of ! marks written after the value, e.g. (5!!)
+
     @abstractmethod
     def to_string(self, tabs: int = 0) -> str:
         """Convert node to indented string representation for debugging."""
@@ -170,6 +179,35 @@ def get_expr_first_token(expr: ExpressionTreeNode) -> Optional[Token]:
             return get_expr_first_token(expr.value) or get_expr_first_token(expr.index)
 
 
+def escape_crosses(text: str, boundary: int) -> bool:
+    """Whether an escape sequence in text starts before boundary and ends after it."""
+    return any(
+        m.start() < boundary < m.end() for m in ESCAPE_PATTERN.finditer(text)
+    )
+
+
+def concatenate_string_literals(strings: list[Token]) -> ExpressionTreeNode:
+    """Join adjacent string literals into one string.
+
+    Plain literals are merged while parsing. An interpolated ${} string is kept
+    separate and added at runtime, so merging can never create a new ${}. The same
+    goes for an escape that would run across the join, like "\\u" "{41}": each
+    literal's escapes are decoded on their own.
+    """
+    parts: list[Token] = []
+    for string in strings:
+        merged = parts[-1].value + string.value if parts else "${"
+        if "${" not in merged and not escape_crosses(merged, len(parts[-1].value)):
+            parts[-1] = Token(TokenType.STRING, merged, parts[-1].line, parts[-1].col)
+        else:
+            parts.append(string)
+    node: ExpressionTreeNode = ValueNode(parts[0])
+    for string in parts[1:]:
+        plus = Token(TokenType.ADD, "+", string.line, string.col)
+        node = ExpressionNode(node, ValueNode(string), OperatorType.ADD, plus)
+    return node
+
+
 def build_expression_tree(
     filename: str, tokens: list[Token], code: str
 ) -> ExpressionTreeNode:
@@ -188,7 +226,11 @@ def build_expression_tree(
     for token in tokens[1:-1]:
         if token.type == TokenType.WHITESPACE and "\t" in token.value:
             raise_error_at_token(
-                filename, code, "Tabs are not allowed in expressions.", token
+                filename,
+                code,
+                "Tabs are not allowed in expressions.",
+                token,
+                error_type=ParseError,
             )
         elif token.type == TokenType.NEWLINE:
             raise_error_at_token(
@@ -196,6 +238,7 @@ def build_expression_tree(
                 code,
                 "Due to the laws of significant whitespace, no newline characters are permitted in expressions. If your code is so long that it needs newlines, consider rewriting it :)",
                 token,
+                error_type=ParseError,
             )
 
     # create a new list consisting and tokens and a brand new type: the list
@@ -213,8 +256,17 @@ def build_expression_tree(
 
     # transform a list of tokens to include operators
     # find the operator with the maximum whitespace between it and other things
-    updated_list = [STR_TO_OPERATOR.get(token.value, token) for token in tokens]
+    # a string like "+" is a value, not an operator
+    updated_list = [
+        (
+            token
+            if token.type == TokenType.STRING
+            else STR_TO_OPERATOR.get(token.value, token)
+        )
+        for token in tokens
+    ]
     max_width, max_index = -1, -1
+    operator_widths: list[tuple[int, int]] = []  # (index, width) of binary operators
     bracket_layers = 0
     for i in range(len(updated_list)):
         if tokens[i].type == TokenType.L_SQUARE:
@@ -245,7 +297,9 @@ def build_expression_tree(
                         code,
                         "Whitespace must be equal on either side of an operator.",
                         tokens[i],
+                        error_type=ParseError,
                     )
+                operator_widths.append((i, r_len))
                 if r_len >= max_width:
                     max_width = r_len
                     max_index = i
@@ -256,6 +310,7 @@ def build_expression_tree(
                     code,
                     "Operator cannot be at the end of an expression.",
                     tokens[i],
+                    error_type=ParseError,
                 )
 
     # detecting single argument function
@@ -303,6 +358,15 @@ def build_expression_tree(
     # value, like a list, name, or anything else
     if max_index == -1:
 
+        # trailing !s inside parentheses mark the confidence of this value
+        if tokens_without_whitespace[-1].type == TokenType.CONFIDENCE:
+            bang_index = tokens.index(tokens_without_whitespace[-1])
+            node = build_expression_tree(
+                filename, tokens[:bang_index] + tokens[bang_index + 1 :], code
+            )
+            node.confidence = len(tokens[bang_index].value)
+            return node
+
         # just making sure the input is correct
         try:
             name_or_value = tokens_without_whitespace[0]
@@ -316,12 +380,23 @@ def build_expression_tree(
                     code,
                     "Expected name or value.",
                     tokens_without_whitespace[0],
+                    error_type=ParseError,
                 )
         except IndexError:
             raise_error_at_token(
-                filename, code, "Expected name or value.", tokens_without_whitespace[0]
+                filename,
+                code,
+                "Expected name or value.",
+                tokens_without_whitespace[0],
+                error_type=ParseError,
             )
 
+        # "Hello, " "world" is one string, like in C
+        if len(tokens_without_whitespace) > 1 and all(
+            t.type == TokenType.STRING for t in tokens_without_whitespace
+        ):
+            return concatenate_string_literals(tokens_without_whitespace)
+
         # this is a list :)
         if name_or_value.type == TokenType.L_SQUARE:
             bracket_layers = 1
@@ -362,6 +437,7 @@ def build_expression_tree(
                                 code,
                                 "Whitespace between either bracket of a list must be equal in length.",
                                 tokens[len(tokens) - int(ends_with_whitespace) - 2],
+                                error_type=ParseError,
                             )
 
                         # now go through all the commas and check if the whitespace is significant
@@ -468,6 +544,7 @@ def build_expression_tree(
                 code,
                 "Expected function call. This is likely an issue of whitespace, as Gulf of Mexico replaces parentheses with spaces and has significant whitespace.",
                 tokens_without_whitespace[0],
+                error_type=ParseError,
             )
 
         all_commas = []
@@ -504,11 +581,30 @@ def build_expression_tree(
         operator = updated_list[max_index]
         if not isinstance(operator, OperatorType):
             raise_error_at_token(
-                filename, code, "Something went wrong. My bad.", tokens[max_index]
+                filename,
+                code,
+                "Something went wrong. My bad.",
+                tokens[max_index],
+                error_type=ParseError,
             )
-        return ExpressionNode(
-            build_expression_tree(filename, tokens[:max_index], code),
-            build_expression_tree(filename, tokens[max_index + 1 :], code),
-            operator=operator,
-            operator_token=tokens[max_index],
-        )
+        # every operator as wide as the split one, back to the last comma that
+        # wide, would be split on next by the left side, so split on all of them
+        # at once and fold them left to right; long chains like 1+1+...+1 then
+        # take a single pass
+        splits: list[int] = []
+        for i, width in operator_widths:
+            if width != max_width:
+                continue
+            if updated_list[i] == OperatorType.COM:
+                splits = []
+            else:
+                splits.append(i)
+        node = build_expression_tree(filename, tokens[: splits[0]], code)
+        for i, next_i in zip(splits, [*splits[1:], len(tokens)]):
+            node = ExpressionNode(
+                node,
+                build_expression_tree(filename, tokens[i + 1 : next_i], code),
+                operator=updated_list[i],  # type: ignore
+                operator_token=tokens[i],
+            )
+        return node
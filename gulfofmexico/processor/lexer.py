@@ -7,24 +7,43 @@ string quoting system where matching quote counts determine string boundaries.
 Key Features:
     - Flexible quoting: "" or '"' both create strings
     - Quote count matching: equal counts on both sides define string boundaries
-    - Single-line comments with //
+    - Single-line comments with // and block comments with /* */ (not nested)
+    - CRLF and lone CR line endings are treated as LF
     - Whitespace preservation for indentation enforcement
     - Special empty value () tokenized as blank name
+    - Unknown ASCII characters (@, #, $, ...) raise an error unless lenient
+    - ! inside parentheses is a confidence marker on a value, not a statement end
 
 Token Types Generated:
-    - Names (variables/keywords): alphanumeric sequences
+    - Names (variables/keywords): alphanumeric sequences, interned
     - Strings: quoted sequences with count matching
-    - Numbers: handled during parsing, not lexing
+    - Numbers: lexed as names and turned into values by the interpreter; the
+      sign of an exponent (2E-4) stays part of the number, and _ may separate
+      digits (1_000_000)
     - Operators: +, -, *, /, ^, ==, ===, ====, etc.
+    - Compound assignment: +=, -=, *=, /=
     - Delimiters: {}, [], (), :, ;, |, &
 
 Inspired by: https://craftinginterpreters.com/scanning.html
 """
 
 from __future__ import annotations
+import re
+import sys
 from typing import Optional
 
-from gulfofmexico.base import Token, TokenType, ALPH_NUMS, raise_error_at_line
+from gulfofmexico.base import (
+    ALPH_NUMS,
+    ParseError,
+    Token,
+    TokenType,
+    normalize_newlines,
+    raise_error_at_line,
+)
+
+# when set (see --lenient), stray characters like @ or # are lexed as part of a name
+# instead of raising an error
+allow_unknown_characters: bool = False
 
 
 def add_to_tokens(
@@ -39,6 +58,61 @@ def add_to_tokens(
     )
 
 
+# tokens that can end a value, so a - right after them is subtraction
+VALUE_END_TOKENS = frozenset({TokenType.NAME, TokenType.STRING, TokenType.R_SQUARE})
+
+
+def last_significant_type(tokens: list[Token]) -> Optional[TokenType]:
+    for token in reversed(tokens):
+        if token.type != TokenType.WHITESPACE:
+            return token.type
+    return None
+
+
+def is_compound_assign(code: str, curr: int) -> bool:
+    """Whether the operator at code[curr] starts +=, -=, *= or /= (but not +== etc)."""
+    return code[curr + 1] == "=" and code[curr + 2] != "="
+
+
+# a number that so far ends in its exponent marker, like 2E or 1.5e
+EXPONENT_START = re.compile(r"[0-9][0-9_]*(\.[0-9_]+)?[eE]")
+
+
+def is_exponent_sign(code: str, name_start: int, curr: int) -> bool:
+    """Whether the + or - at code[curr] is the sign of an exponent, as in 2E-4."""
+    return (
+        code[curr] in "+-"
+        and code[curr + 1].isdigit()
+        and EXPONENT_START.fullmatch(code, name_start, curr) is not None
+    )
+
+
+def is_number_literal(value: str) -> bool:
+    """Whether value reads as a number, digit separators included (1_000, 0xFF)."""
+    if not value[:1].isdigit():
+        return False
+    try:
+        if value[:2].lower() in {"0x", "0b", "0o"}:
+            int(value, 0)
+        else:
+            float(value)
+    except ValueError:
+        return False
+    return True
+
+
+def is_misplaced_digit_separator(value: str) -> bool:
+    """Whether value would be a number without its underscores, but isn't with them.
+
+    Only words starting with a digit count, since _1000 is an ordinary name.
+    """
+    return (
+        value[:1].isdigit()
+        and not is_number_literal(value)
+        and is_number_literal(value.replace("_", ""))
+    )
+
+
 def get_effective_whitespace_value(char: str) -> str:
     match char:
         case " " | "(":
@@ -78,25 +152,33 @@ def get_string_token(
         '""hello world"'"  <-- this is interpreted as the string "hello world"
     Therefore, to avoid premature returns of quotes, simply preface your quotes with a single ' and the rest "
     This guarantees that no pair of quotes will be found in the starting quote because it will have an odd number of quotes.
+
+    A run of quotes directly followed by string content (""hello"", '''a''') is treated as a
+    single opening group, so the string only closes on a group with the same quote count.
     """
 
-    quote_value = ""
-    while code[curr] in """"'""":  # lmaoo
-        quote_value += code[curr]
-        if is_matching_pair(quote_value):
-            return curr, ""
-        curr += 1
+    run_start = run_end = curr
+    while code[run_end] in """"'""":
+        run_end += 1
+    if code[run_end] not in ALPH_NUMS and code[run_end] != "$":
+        quote_value = ""
+        while code[curr] in """"'""":  # lmaoo
+            quote_value += code[curr]
+            if is_matching_pair(quote_value):
+                return curr, ""
+            curr += 1
+    quote_value = code[run_start:run_end]
+    curr = run_end
     quote_count = get_quote_count(quote_value)
 
-    value = ""
+    # the string body is one contiguous run of the source, sliced out once it closes
     while curr < len(code):
         running_count, quote_start = 0, curr
         while code[curr] in """"'""":
             running_count += 2 if code[curr] == '"' else 1
             if running_count == quote_count:
-                return curr, value
+                return curr, code[run_end:quote_start]
             curr += 1
-        value += code[quote_start : curr + 1]
         curr += 1
     else:
         raise_error_at_line(
@@ -104,19 +186,25 @@ def get_string_token(
             code,
             error_line,
             "Invalid string. Starting quotes do not match opening quotes.",
+            error_type=ParseError,
         )
 
 
 def tokenize(filename: str, code: str) -> list[Token]:
+    # CRLF and CR files lex exactly like LF ones, strings included
+    code = normalize_newlines(code)
     code += "   "  # adding a space here so i dont have to write 10 damn checks for out of bounds
     line_count = 1
     tokens = []
     curr, start = 0, 0
+    paren_depth = 0  # parens are whitespace, but a ! inside them doesn't end a statement
+    carried_whitespace = ""  # whitespace from before a /* */ comment
     while curr < len(code):
         match code[curr]:
             case "\n":
                 line_count += 1
-                start = curr  # at the new line to get col number
+                paren_depth = 0
+                start = curr + 1  # columns count from 0 at the start of each line
                 add_to_tokens(tokens, line_count, curr - start, TokenType.NEWLINE)
             case "}":
                 add_to_tokens(tokens, line_count, curr - start, TokenType.R_CURLY)
@@ -147,6 +235,15 @@ def tokenize(filename: str, code: str) -> list[Token]:
                     )
             case ",":
                 add_to_tokens(tokens, line_count, curr - start, TokenType.COMMA)
+            case "+" | "-" | "*" | "/" if is_compound_assign(code, curr):
+                curr += 1
+                add_to_tokens(
+                    tokens,
+                    line_count,
+                    curr - start,
+                    TokenType.COMPOUND_ASSIGN,
+                    code[curr - 1 : curr + 1],
+                )
             case "+":
                 if code[curr + 1] == "+":
                     add_to_tokens(tokens, line_count, curr - start, TokenType.INCREMENT)
@@ -170,6 +267,36 @@ def tokenize(filename: str, code: str) -> list[Token]:
                         curr += 1
                     # Don't add curr += 1 at end, let next iteration handle the \n
                     continue
+                elif code[curr + 1] == "*":
+                    # block comments don't nest, the first */ closes them
+                    comment_end = code.find("*/", curr + 2)
+                    if comment_end == -1:
+                        raise_error_at_line(
+                            filename,
+                            code,
+                            line_count,
+                            "Unterminated block comment.",
+                            error_type=ParseError,
+                        )
+                    if (newlines := code.count("\n", curr, comment_end)) > 0:
+                        line_count += newlines
+                        start = code.rfind("\n", curr, comment_end) + 1
+                    curr = comment_end + 1
+                    indented = tokens and tokens[-1].type == TokenType.WHITESPACE
+                    before = len(tokens) - 1 if indented else len(tokens)
+                    if not before or tokens[before - 1].type == TokenType.NEWLINE:
+                        # a comment that starts a line keeps the indentation before
+                        # it, so the spaces after it are dropped
+                        while curr + 1 < len(code) and code[curr + 1] in " \t":
+                            curr += 1
+                    # whitespace on both sides of a comment is lexed as one run
+                    elif (
+                        tokens
+                        and tokens[-1].type == TokenType.WHITESPACE
+                        and code[curr + 1] in " ()\t"
+                        and code[curr + 1 : curr + 3] != "()"
+                    ):
+                        carried_whitespace = tokens.pop().value
                 else:
                     add_to_tokens(tokens, line_count, curr - start, TokenType.DIVIDE)
             case "^":
@@ -190,6 +317,11 @@ def tokenize(filename: str, code: str) -> list[Token]:
                         tokens, line_count, curr - start, TokenType.LESS_EQUAL
                     )
                     curr += 1
+                elif code[curr + 1] == "-":  # so x < -1 needs a space
+                    add_to_tokens(
+                        tokens, line_count, curr - start, TokenType.ASSIGN_ARROW
+                    )
+                    curr += 1
                 else:
                     add_to_tokens(tokens, line_count, curr - start, TokenType.LESS_THAN)
             case "!":
@@ -197,7 +329,13 @@ def tokenize(filename: str, code: str) -> list[Token]:
                 while code[curr + 1] == "!":
                     value += "!"
                     curr += 1
-                add_to_tokens(tokens, line_count, curr - start, TokenType.BANG, value)
+                add_to_tokens(
+                    tokens,
+                    line_count,
+                    curr - start,
+                    TokenType.CONFIDENCE if paren_depth else TokenType.BANG,
+                    value,
+                )
             case "?":
                 value = "?"
                 while code[curr + 1] == "?":
@@ -209,6 +347,7 @@ def tokenize(filename: str, code: str) -> list[Token]:
                         code,
                         line_count,
                         "User is too confused. Aborting due to trust issues.",
+                        error_type=ParseError,
                     )  # heheheheheheh
                 add_to_tokens(
                     tokens, line_count, curr - start, TokenType.QUESTION, value
@@ -228,8 +367,13 @@ def tokenize(filename: str, code: str) -> list[Token]:
                         tokens, line_count, curr - start, TokenType.EQUAL, value
                     )
             case '"' | "'":
+                string_start = curr
                 curr, value = get_string_token(code, curr, filename, line_count)
                 add_to_tokens(tokens, line_count, curr - start, TokenType.STRING, value)
+                # keep line numbers in sync after strings that span multiple lines
+                if (newlines := code.count("\n", string_start, curr)) > 0:
+                    line_count += newlines
+                    start = code.rfind("\n", string_start, curr) + 1
             case " " | "\t" | "(" | ")":
                 if code[curr] == "(" and curr + 1 < len(code) and code[curr + 1] == ")":
                     add_to_tokens(
@@ -243,18 +387,63 @@ def tokenize(filename: str, code: str) -> list[Token]:
                     )
                     curr += 1
                 else:
-                    value = get_effective_whitespace_value(code[curr])
+                    run_start = curr
+                    value = carried_whitespace + get_effective_whitespace_value(
+                        code[curr]
+                    )
+                    carried_whitespace = ""
                     while curr + 1 < len(code) and code[curr + 1] in " ()\t":
                         value += get_effective_whitespace_value(code[curr + 1])
                         curr += 1
-                    add_to_tokens(
-                        tokens, line_count, curr - start, TokenType.WHITESPACE, value
-                    )
+                    run = code[run_start : curr + 1]
+                    paren_depth = max(0, paren_depth + run.count("(") - run.count(")"))
+                    # (2)-1 is subtraction: a bare ) between a value and - must not
+                    # leave whitespace that makes the - look like a negative sign
+                    if not (
+                        value == ""
+                        and code[curr + 1] == "-"
+                        and last_significant_type(tokens) in VALUE_END_TOKENS
+                    ):
+                        add_to_tokens(
+                            tokens,
+                            line_count,
+                            curr - start,
+                            TokenType.WHITESPACE,
+                            value,
+                        )
             case c:
-                value = c
-                while code[curr + 1] in ALPH_NUMS:
+                if (
+                    not allow_unknown_characters
+                    and c.isascii()
+                    and c not in ALPH_NUMS
+                    and not c.isspace()
+                ):
+                    raise_error_at_line(
+                        filename,
+                        code,
+                        line_count,
+                        f"Unknown character {c!r}. Remove it or put it inside a string.",
+                        error_type=ParseError,
+                    )
+                name_start = curr
+                while code[curr + 1] in ALPH_NUMS or is_exponent_sign(
+                    code, name_start, curr + 1
+                ):
                     curr += 1
-                    value += code[curr]
-                add_to_tokens(tokens, line_count, curr - start, TokenType.NAME, value)
+                value = code[name_start : curr + 1]
+                if "_" in value and is_misplaced_digit_separator(value):
+                    raise_error_at_line(
+                        filename,
+                        code,
+                        line_count,
+                        f"Misplaced _ in the number {value}. "
+                        "Underscores can only go between two digits.",
+                        error_type=ParseError,
+                    )
+                # interned so every mention of a name, and the namespace keys made
+                # from them, share one string and compare by identity first
+                add_to_tokens(
+                    tokens, line_count, curr - start, TokenType.NAME, sys.intern(value)
+                )
         curr += 1
     return tokens
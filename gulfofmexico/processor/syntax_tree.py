@@ -10,11 +10,14 @@ Statement Types:
     - VariableDeclaration: const/var declarations with confidence and lifetime
     - VariableAssignment: assignment with optional indexing
     - Conditional: if statements with expression evaluation
+    - MatchStatement: run the first arm whose pattern equals a value
+    - RepeatStatement: run a block a counted number of times
     - WhenStatement: reactive when triggers on variable changes
     - AfterStatement: scheduled execution with temporal delays
     - ReturnStatement: function returns with optional debug
     - DeleteStatement: value deletion from memory
     - ReverseStatement: reverse string/list/time (special operator)
+    - DebugStatement: pause and dump the local names
     - ExpressionStatement: standalone expressions
     - ExportStatement: export values to other file sections
     - ImportStatement: import from file sections or external files
@@ -25,22 +28,33 @@ Key Features:
     - Type annotations: optional type hints after colons
     - Lifetimes: <5.0> for temporal, 100 for line-based
 
-These AST nodes are generated by generate_syntax_tree() in this module,
-then executed by pattern matching in interpreter.py.
+These AST nodes are generated by generate_syntax_tree() in this module (or one
+at a time by iter_syntax_tree() / parse_next_statement()), then executed by
+pattern matching in interpreter.py.
 """
 
 from abc import ABCMeta
-from typing import Optional, Union
+from typing import Iterator, Optional, Union
 from dataclasses import dataclass
 
 from gulfofmexico.base import (
     STR_TO_OPERATOR,
+    InterpretationError,
+    ParseError,
     Token,
     TokenType,
     raise_error_at_line,
     raise_error_at_token,
 )
-from gulfofmexico.processor.expression_tree import ExpressionTreeNode
+from gulfofmexico.processor.expression_tree import (
+    ExpressionNode,
+    ExpressionTreeNode,
+    build_expression_tree,
+)
+
+# when set (see --all-errors), a statement that fails to parse is skipped and every
+# error is reported together at the end instead of stopping at the first one
+collect_parse_errors: bool = False
 
 __all__ = [
     "FunctionDefinition",
@@ -48,9 +62,12 @@ __all__ = [
     "VariableDeclaration",
     "VariableAssignment",
     "Conditional",
+    "MatchStatement",
+    "RepeatStatement",
     "ReturnStatement",
     "DeleteStatement",
     "ReverseStatement",
+    "DebugStatement",
     "ExpressionStatement",
     "WhenStatement",
     "AfterStatement",
@@ -87,6 +104,7 @@ class FunctionDefinition(CodeStatement):
     Examples:
         fn add(a, b) => a + b!
         async function process(data) => {...}!
+        function count(): Number => {...}!
     """
 
     keywords: list[Token]  # ['async', 'function'] or just ['function']
@@ -94,6 +112,7 @@ class FunctionDefinition(CodeStatement):
     args: list[Token]
     code: list[tuple[CodeStatement, ...]]
     is_async: bool
+    return_type: Optional[list[Token]] = None  # return type annotation tokens
 
 
 @dataclass
@@ -157,8 +176,41 @@ class VariableAssignment(CodeStatement, CodeStatementDebuggable):
 class Conditional(CodeStatement, CodeStatementKeywordable):
     """If statement with expression condition.
 
-    Condition evaluated for boolean value (true/false/maybe).
-    Maybe values handled probabilistically.
+    Condition evaluated for boolean value (true/false/maybe). An optional
+    maybe { } branch runs for maybe and an optional else { } branch for false.
+    Without a maybe branch, maybe is handled probabilistically.
+    """
+
+    keyword: Token
+    expression: Union[list[Token], ExpressionTreeNode]
+    code: list[tuple[CodeStatement, ...]]
+    maybe_code: Optional[list[tuple[CodeStatement, ...]]] = None
+    else_code: Optional[list[tuple[CodeStatement, ...]]] = None
+
+
+# name expression { expression => { } (, expression => { })* (, _ => { })? }
+@dataclass
+class MatchStatement(CodeStatement, CodeStatementKeywordable):
+    """Match statement comparing one value against each arm's pattern with ==.
+
+    The first arm whose pattern equals the value runs. An optional _ arm, which
+    must come last, runs when no pattern matches.
+    """
+
+    keyword: Token
+    expression: Union[list[Token], ExpressionTreeNode]
+    arms: list[
+        tuple[Union[list[Token], ExpressionTreeNode], list[tuple[CodeStatement, ...]]]
+    ]
+    default_code: Optional[list[tuple[CodeStatement, ...]]] = None
+
+
+# name expression { }
+@dataclass
+class RepeatStatement(CodeStatement, CodeStatementKeywordable):
+    """Counted loop running its body once per unit of the floored count.
+
+    A zero or negative count runs the body zero times.
     """
 
     keyword: Token
@@ -192,6 +244,13 @@ class ReverseStatement(
     debug: int
 
 
+# debug!
+@dataclass
+class DebugStatement(CodeStatement, CodeStatementKeywordable, CodeStatementDebuggable):
+    keyword: Token
+    debug: int
+
+
 # expression !?   < virtually indistinguishable from a return statement from a parsing perspective
 @dataclass
 class ExpressionStatement(CodeStatement, CodeStatementDebuggable):
@@ -216,6 +275,7 @@ class AfterStatement(CodeStatement, CodeStatementKeywordable):
 
 
 # name name (, name)* name string!
+# name * name string!
 @dataclass
 class ExportStatement(CodeStatement, CodeStatementDebuggable):
     export_keyword: Token
@@ -223,6 +283,7 @@ class ExportStatement(CodeStatement, CodeStatementDebuggable):
     to_keyword: Token
     target_file: Token
     debug: int
+    export_all: bool = False  # export * exports every non-builtin name in scope
 
 
 # name
@@ -234,24 +295,112 @@ class ImportStatement(CodeStatement, CodeStatementKeywordable, CodeStatementDebu
 
 
 # idea: create a class that evaluates at runtime what a statement is, so then execute it
-def split_into_statements(tokens: list[Token]) -> list[list[Token]]:
-    statements = [[]]
+# keywords that are followed by an expression, so a ; right after them is NOT
+EXPRESSION_KEYWORDS = frozenset({"if", "when", "return", "after", "await"})
+
+
+def is_statement_separator(tokens: list[Token], i: int) -> bool:
+    """Decide whether the ; at tokens[i] ends a statement or is the NOT operator.
+
+    A ; after a complete value (name, string, or closing bracket, ignoring spaces) and
+    followed by whitespace ends the statement: x = 1; y = 2! and x ; y! are two statements.
+    A ; in operand position is NOT: ;x, a & ;b, print ;x, if ; ready {.
+    """
+    if tokens[i].type != TokenType.SEMICOLON:
+        return False
+    if i + 1 < len(tokens) and tokens[i + 1].type not in {
+        TokenType.WHITESPACE,
+        TokenType.NEWLINE,
+    }:
+        return False
+    prev = i - 1
+    while prev >= 0 and tokens[prev].type == TokenType.WHITESPACE:
+        prev -= 1
+    if prev < 0 or tokens[prev].value in EXPRESSION_KEYWORDS:
+        return False
+    return tokens[prev].type in {
+        TokenType.NAME,
+        TokenType.STRING,
+        TokenType.R_SQUARE,
+    }
+
+
+# keywords that continue an if statement with another { } branch after its }
+CONDITIONAL_BRANCH_KEYWORDS = ("maybe", "else")
+
+# keywords that start a { } block statement. Any other name may be an alias of one
+# (const const zz = if!), which only the interpreter can resolve
+BLOCK_KEYWORDS = frozenset(
+    {"if", "when", "after", "repeat", "match", "class", "className", "function"}
+)
+
+
+def is_function_head(statement: list[Token]) -> bool:
+    """Whether a => comes before the first { of statement, as in f(x) => { }."""
+    for token in statement:
+        if token.type == TokenType.L_CURLY:
+            return False
+        if token.type == TokenType.FUNC_POINT:
+            return True
+    return False
+
+
+def may_be_keyword(token: Token, keyword: str) -> bool:
+    """Whether token may start a keyword statement, spelled out or as an alias."""
+    return token.value == keyword or (
+        token.type == TokenType.NAME and token.value not in BLOCK_KEYWORDS
+    )
+
+
+def starts_conditional_branch(tokens: list[Token], i: int) -> bool:
+    """Whether tokens[i:] (ignoring whitespace) is maybe { or else {."""
+    significant = (
+        t for t in tokens[i:] if t.type not in {TokenType.WHITESPACE, TokenType.NEWLINE}
+    )
+    first, second = next(significant, None), next(significant, None)
+    return (
+        first is not None
+        and first.type == TokenType.NAME
+        and first.value in CONDITIONAL_BRANCH_KEYWORDS
+        and second is not None
+        and second.type == TokenType.L_CURLY
+    )
+
+
+def matching_curly_index(tokens: list[Token], open_index: int) -> int:
+    """Index of the } closing the { at tokens[open_index], or -1 if it never closes."""
+    depth = 0
+    for i in range(open_index, len(tokens)):
+        if tokens[i].type == TokenType.L_CURLY:
+            depth += 1
+        elif tokens[i].type == TokenType.R_CURLY:
+            depth -= 1
+            if depth == 0:
+                return i
+    return -1
+
+
+def next_statement_tokens(tokens: list[Token], start: int) -> tuple[list[Token], int]:
+    """Collect the tokens of the statement beginning at tokens[start].
+
+    Returns the statement without surrounding whitespace and newlines, and the index
+    just past it. The statement is empty once only whitespace is left.
+    """
+    statement = []
     bracket_layers = 0
-    for token in tokens:
+    i = start
+    while i < len(tokens):
+        token = tokens[i]
+        i += 1
 
-        # check for expression-ending newlines
-        if (
-            token.type == TokenType.WHITESPACE and not statements[-1]
-        ):  # don't care about whitespace at the beginning or end of an expression, idk
+        # a separating ; ends the statement just like !
+        if bracket_layers == 0 and is_statement_separator(tokens, i - 1):
+            token = Token(TokenType.BANG, "!", token.line, token.col)
+
+        # don't care about whitespace at the beginning of an expression, idk
+        if token.type in {TokenType.WHITESPACE, TokenType.NEWLINE} and not statement:
             continue
-        if (
-            token.type == TokenType.NEWLINE
-            and not statements[-1]
-            and len(statements) > 1
-        ):
-            statements[-2].append(token)
-        else:
-            statements[-1].append(token)
+        statement.append(token)
 
         # this is the start of a new scope, we don't care about those for rn in terms of starting a new statement
         if token.type == TokenType.L_CURLY:
@@ -263,30 +412,33 @@ def split_into_statements(tokens: list[Token]) -> list[list[Token]]:
             token.type in [TokenType.R_CURLY, TokenType.BANG, TokenType.QUESTION]
             and bracket_layers == 0
         ):
-            while (
-                statements[-1][-1].type == TokenType.NEWLINE
-            ):  # remove newlines at the end of a statement
-                statements[-1].pop()
-            statements.append([])
+            # if x { } maybe { } else { } is one statement
+            if (
+                token.type == TokenType.R_CURLY
+                and may_be_keyword(statement[0], "if")
+                and not is_function_head(statement)
+                and starts_conditional_branch(tokens, i)
+            ):
+                continue
+            break
 
-    # remove stray newlines cause they are annoying and shit, also remove empty tings
-    final_statements = []
-    for statement in statements:
-        while statement and statement[-1].type in {
-            TokenType.WHITESPACE,
-            TokenType.NEWLINE,
-        }:  ## NOTE: END WILL NEVER BE WHITESPACE
-            statement.pop()
-        while statement and statement[0].type in {
-            TokenType.WHITESPACE,
-            TokenType.NEWLINE,
-        }:  ## NOTE: NOW START WILL NEVER BE WHITESPACE EITHER
-            statement.pop(0)
-        if not statement:
-            continue
-        final_statements.append(statement)
+    # remove stray newlines cause they are annoying and shit
+    while statement and statement[-1].type in {
+        TokenType.WHITESPACE,
+        TokenType.NEWLINE,
+    }:  ## NOTE: END WILL NEVER BE WHITESPACE
+        statement.pop()
+    return statement, i
 
-    return final_statements
+
+def split_into_statements(tokens: list[Token]) -> list[list[Token]]:
+    statements = []
+    start = 0
+    while start < len(tokens):
+        statement, start = next_statement_tokens(tokens, start)
+        if statement:
+            statements.append(statement)
+    return statements
 
 
 def extract_type_annotations(
@@ -322,6 +474,8 @@ def extract_type_annotations(
                 i += 1
                 while i < len(tokens) and tokens[i].type not in [
                     TokenType.EQUAL,
+                    TokenType.ASSIGN_ARROW,
+                    TokenType.FUNC_POINT,
                     TokenType.L_CURLY,
                 ]:
                     if tokens[i].type != TokenType.WHITESPACE:
@@ -367,11 +521,11 @@ def remove_type_hints(
                 ref_square_bracket_layers = square_bracket_layers  # prob gonna be zero but idek imma just do this
             if not adding_tokens:
 
-                # check if it is at an operator
+                # check if it is at an operator or the start of a function body
                 if (
                     STR_TO_OPERATOR.get(t.value)
-                    and square_bracket_layers == ref_square_bracket_layers
-                ):
+                    or t.type in {TokenType.ASSIGN_ARROW, TokenType.FUNC_POINT}
+                ) and square_bracket_layers == ref_square_bracket_layers:
                     adding_tokens = True
 
                 # adjust for Name<...> things, which also allows regex to pass too
@@ -390,6 +544,7 @@ def remove_type_hints(
                             code,
                             "Something went wrong parsing type hints (a.k.a. removing them).",
                             t,
+                            error_type=ParseError,
                         )
 
             if (
@@ -417,6 +572,7 @@ def assert_proper_indentation(filename: str, tokens: list[Token], code: str) ->
                     code,
                     "Invalid indenting detected (must be a multiple of 3). Tabs count as 2 spaces.",
                     t,
+                    error_type=ParseError,
                 )
             looking_for_whitespace = False
 
@@ -426,6 +582,7 @@ def create_function_definition(
     without_whitespace: list[Token],
     code: str,
     statements_inside_scope: list[tuple[CodeStatement, ...]],
+    return_type: Optional[list[Token]] = None,
 ) -> tuple[CodeStatement, ...]:
 
     # Parse function declaration
@@ -442,6 +599,7 @@ def create_function_definition(
             code,
             "Insufficient keyword count in function declaration.",
             without_whitespace[0],
+            error_type=ParseError,
         )
 
     # Check if async
@@ -461,6 +619,7 @@ def create_function_definition(
             code,
             "Function declaration must have a name.",
             without_whitespace[0],
+            error_type=ParseError,
         )
 
     # Now collect all remaining parameter names (skipping commas)
@@ -485,6 +644,7 @@ def create_function_definition(
             args=args,
             code=statements_inside_scope,
             is_async=is_async,
+            return_type=return_type,
         ),
     )
 
@@ -507,6 +667,7 @@ def create_scoped_code_statement(
             code,
             "End of statement with open scope must close the scope.",
             tokens[-1],
+            error_type=ParseError,
         )
     if without_whitespace[0].type != TokenType.NAME:
         raise_error_at_token(
@@ -514,12 +675,45 @@ def create_scoped_code_statement(
             code,
             "Scoped code statement must start with a keyword.",
             without_whitespace[0],
+            error_type=ParseError,
         )
 
     # at this point, can be when, class dec, function call, or if statement
     scope_open_index = [t.type == TokenType.L_CURLY for t in tokens].index(True)
+    scope_close_index = matching_curly_index(tokens, scope_open_index)
+    if scope_close_index < len(tokens) - ends_with_punc - 1:
+        # more scopes follow the first one, so this has to be an if with branches
+        return create_conditional_with_branches(
+            filename,
+            tokens,
+            code,
+            scope_open_index,
+            scope_close_index,
+            tokens[scope_close_index + 1 : len(tokens) - ends_with_punc],
+        )
     stuff_inside_scope = tokens[scope_open_index + 1 : len(tokens) - ends_with_punc - 1]
-    statements_inside_scope = generate_syntax_tree(filename, stuff_inside_scope, code)
+    if without_whitespace[0].value == "match":
+        # the scope holds pattern => { } arms rather than statements
+        return create_match_statement(
+            filename, tokens, code, scope_open_index, stuff_inside_scope
+        )
+    match_possibilities: tuple[CodeStatement, ...] = ()
+    if may_be_keyword(without_whitespace[0], "match") and not is_function_head(tokens):
+        # an alias of match, if the scope holds arms; the interpreter decides
+        try:
+            match_possibilities = create_match_statement(
+                filename, tokens, code, scope_open_index, stuff_inside_scope
+            )
+        except InterpretationError:
+            pass
+    try:
+        statements_inside_scope = generate_syntax_tree(
+            filename, stuff_inside_scope, code
+        )
+    except InterpretationError:
+        if not match_possibilities:
+            raise
+        return match_possibilities
 
     # see the function pointer -> immediately know
     can_be_function = any(
@@ -556,15 +750,23 @@ def create_scoped_code_statement(
     # is no point in doing anything extra special
 
     # this dude is separated to another function because the same code is reused in () => ... functions (no scope)
-    possibilities = []
+    possibilities = list(match_possibilities)
     if can_be_function:
         return create_function_definition(
-            filename, without_whitespace, code, statements_inside_scope
+            filename,
+            without_whitespace,
+            code,
+            statements_inside_scope,
+            return_type=type_annotation,
         )
 
     if can_be_function_block:
         return create_function_definition(
-            filename, without_whitespace, code, statements_inside_scope
+            filename,
+            without_whitespace,
+            code,
+            statements_inside_scope,
+            return_type=type_annotation,
         )
 
     if can_be_class:
@@ -601,11 +803,151 @@ def create_scoped_code_statement(
                 ],
                 code=statements_inside_scope,
             ),
+            RepeatStatement(
+                keyword=without_whitespace[0],
+                expression=tokens[
+                    int(tokens[0].type == TokenType.WHITESPACE) + 1 : scope_open_index
+                ],
+                code=statements_inside_scope,
+            ),
         ]
     )
     return tuple(possibilities)
 
 
+def create_conditional_with_branches(
+    filename: str,
+    tokens: list[Token],
+    code: str,
+    scope_open_index: int,
+    scope_close_index: int,
+    branch_tokens: list[Token],
+) -> tuple[CodeStatement, ...]:
+    """Parse if cond { } followed by maybe { } and/or else { }, in that order."""
+    branches: dict[str, list[tuple[CodeStatement, ...]]] = {}
+    allowed = list(CONDITIONAL_BRANCH_KEYWORDS)
+    curr = 0
+    while curr < len(branch_tokens):
+        if branch_tokens[curr].type in {TokenType.WHITESPACE, TokenType.NEWLINE}:
+            curr += 1
+            continue
+        keyword = branch_tokens[curr]
+        if keyword.value not in allowed or not starts_conditional_branch(
+            branch_tokens, curr
+        ):
+            raise_error_at_token(
+                filename,
+                code,
+                "Expected a maybe { } or else { } branch, each at most once, in that order.",
+                keyword,
+                error_type=ParseError,
+            )
+        allowed = allowed[allowed.index(keyword.value) + 1 :]
+        open_index = [t.type == TokenType.L_CURLY for t in branch_tokens].index(
+            True, curr
+        )
+        close_index = matching_curly_index(branch_tokens, open_index)
+        branches[keyword.value] = generate_syntax_tree(
+            filename, branch_tokens[open_index + 1 : close_index], code
+        )
+        curr = close_index + 1
+
+    without_whitespace = [t for t in tokens if t.type != TokenType.WHITESPACE]
+    return (
+        Conditional(
+            keyword=without_whitespace[0],
+            expression=tokens[
+                int(tokens[0].type == TokenType.WHITESPACE) + 1 : scope_open_index
+            ],
+            code=generate_syntax_tree(
+                filename, tokens[scope_open_index + 1 : scope_close_index], code
+            ),
+            maybe_code=branches.get("maybe"),
+            else_code=branches.get("else"),
+        ),
+    )
+
+
+def create_match_statement(
+    filename: str,
+    tokens: list[Token],
+    code: str,
+    scope_open_index: int,
+    arm_tokens: list[Token],
+) -> tuple[CodeStatement, ...]:
+    """Parse match expr { pattern => { }, ..., _ => { } } into its arms."""
+    arms = []
+    default_code = None
+    curr = 0
+    while curr < len(arm_tokens):
+        if arm_tokens[curr].type in {
+            TokenType.WHITESPACE,
+            TokenType.NEWLINE,
+            TokenType.COMMA,
+        }:
+            curr += 1
+            continue
+        # pattern tokens run up to the =>, then the arm's { } body follows
+        arrow_index = curr
+        while (
+            arrow_index < len(arm_tokens)
+            and arm_tokens[arrow_index].type != TokenType.FUNC_POINT
+        ):
+            arrow_index += 1
+        open_index = arrow_index + 1
+        while (
+            open_index < len(arm_tokens)
+            and arm_tokens[open_index].type == TokenType.WHITESPACE
+        ):
+            open_index += 1
+        pattern = [
+            t
+            for t in arm_tokens[curr:arrow_index]
+            if t.type not in {TokenType.WHITESPACE, TokenType.NEWLINE}
+        ]
+        if (
+            not pattern
+            or open_index >= len(arm_tokens)
+            or arm_tokens[open_index].type != TokenType.L_CURLY
+        ):
+            raise_error_at_token(
+                filename,
+                code,
+                "Expected a match arm of the form pattern => { }.",
+                arm_tokens[curr],
+                error_type=ParseError,
+            )
+        if default_code is not None:
+            raise_error_at_token(
+                filename,
+                code,
+                "The _ arm of a match statement must come last.",
+                pattern[0],
+                error_type=ParseError,
+            )
+        close_index = matching_curly_index(arm_tokens, open_index)
+        body = generate_syntax_tree(
+            filename, arm_tokens[open_index + 1 : close_index], code
+        )
+        if len(pattern) == 1 and pattern[0].value == "_":
+            default_code = body
+        else:
+            arms.append((arm_tokens[curr:arrow_index], body))
+        curr = close_index + 1
+
+    without_whitespace = [t for t in tokens if t.type != TokenType.WHITESPACE]
+    return (
+        MatchStatement(
+            keyword=without_whitespace[0],
+            expression=tokens[
+                int(tokens[0].type == TokenType.WHITESPACE) + 1 : scope_open_index
+            ],
+            arms=arms,
+            default_code=default_code,
+        ),
+    )
+
+
 def is_proper_comma_list(
     without_whitespace: list[Token],
     accepted_tokens: frozenset[TokenType] = frozenset({TokenType.NAME}),
@@ -623,6 +965,85 @@ def is_proper_comma_list(
     return True
 
 
+def assignment_indexes(tokens: list[Token]) -> list[list[Token]]:
+    """The tokens inside each top-level [ ] before the = of an assignment."""
+    indexes: list[list[Token]] = [[]]  # is list[list] to handle multiple indexes
+    bracket_layers, add_to_index = 0, False
+    for t in tokens:
+        if t.type == TokenType.L_SQUARE:
+            if bracket_layers == 0:
+                add_to_index = True
+            bracket_layers += 1
+            continue
+        elif t.type == TokenType.R_SQUARE:
+            bracket_layers -= 1
+            if bracket_layers == 0:
+                add_to_index = False
+                indexes.append([])
+            continue
+        elif (
+            bracket_layers == 0 and t.type == TokenType.EQUAL
+        ):  # exit when hitting the equals
+            break
+
+        if add_to_index:
+            indexes[-1].append(t)
+    indexes.pop()  # the last one will always be empty
+    return indexes
+
+
+def create_compound_assignment(
+    filename: str,
+    tokens: list[Token],
+    code: str,
+    debug_level: int,
+    confidence: int,
+) -> tuple[CodeStatement, ...]:
+    """Parse name op= value! (or name[i] op= value!) as name = name op value!.
+
+    The right side is built on its own first, so x *= 1 + 2! multiplies by 3.
+    """
+    op_index = [t.type == TokenType.COMPOUND_ASSIGN for t in tokens].index(True)
+    op_token = tokens[op_index]
+    target = [t for t in tokens[:op_index] if t.type != TokenType.WHITESPACE]
+    value = tokens[op_index + 1 : -1]
+    if (
+        not target
+        or target[0].type != TokenType.NAME
+        or len(target) > 1
+        and target[1].type != TokenType.L_SQUARE
+        or not any(t.type != TokenType.WHITESPACE for t in value)
+    ):
+        raise_error_at_token(
+            filename,
+            code,
+            f"Expected a name or an index of one before {op_token.value}, "
+            "and a value after it.",
+            op_token,
+            error_type=ParseError,
+        )
+    operator = op_token.value[0]
+    return (
+        VariableAssignment(
+            name=target[0],
+            expression=ExpressionNode(
+                build_expression_tree(filename, target, code),
+                build_expression_tree(filename, value, code),
+                STR_TO_OPERATOR[operator],
+                Token(
+                    TokenType.from_val(operator),  # type: ignore[arg-type]
+                    operator,
+                    op_token.line,
+                    op_token.col,
+                ),
+            ),
+            debug=debug_level,
+            indexes=assignment_indexes(tokens[:op_index]),
+            confidence=confidence,
+        ),
+    )
+
+
 def create_unscoped_code_statement(
     filename: str,
     tokens: list[Token],
@@ -654,6 +1075,12 @@ def create_unscoped_code_statement(
                     ),
                 )
             ],
+            return_type=type_annotation,
+        )
+
+    if any(t.type == TokenType.COMPOUND_ASSIGN for t in tokens):
+        return create_compound_assignment(
+            filename, tokens, code, debug_level, confidence
         )
 
     # import statement: import name, name, name!
@@ -681,6 +1108,15 @@ def create_unscoped_code_statement(
         and without_whitespace[-3].type == TokenType.NAME
     )
 
+    # export everything: export * to string/name!
+    can_be_export_all = (
+        len(without_whitespace) == 5
+        and without_whitespace[0].type == TokenType.NAME
+        and without_whitespace[1].type == TokenType.MULTIPLY
+        and without_whitespace[2].type == TokenType.NAME
+        and without_whitespace[3].type in {TokenType.NAME, TokenType.STRING}
+    )
+
     # let's see what can be what D:
     can_be_return = without_whitespace[0].type == TokenType.NAME
     can_be_delete = (
@@ -695,7 +1131,13 @@ def create_unscoped_code_statement(
             t.type == TokenType.EQUAL and t.value == "=" for t in tokens
         ]
     )
-    can_be_var_assignment = can_be_var_declaration = contains_equals
+    # declarations may also use an arrow: const const x <- 5!
+    tokens_is_declaration_equal = [
+        is_equal or t.type == TokenType.ASSIGN_ARROW
+        for is_equal, t in zip(tokens_is_equal, tokens)
+    ]
+    can_be_var_assignment = contains_equals
+    can_be_var_declaration = any(tokens_is_declaration_equal)
 
     # checking for single name and index for variable assignment
     can_be_var_assignment &= (
@@ -703,34 +1145,9 @@ def create_unscoped_code_statement(
         and without_whitespace[0].type == TokenType.NAME
         and without_whitespace[1].type in {TokenType.EQUAL, TokenType.L_SQUARE}
     )
-    var_assignment_index: list[list[Token]] = [
-        []
-    ]  # is list[list] to handle multiple indexes
-    bracket_layers, add_to_var_assignment_index = 0, False
-    for t in tokens:
-
-        if not can_be_var_assignment:
-            break
-
-        if t.type == TokenType.L_SQUARE:
-            if bracket_layers == 0:
-                add_to_var_assignment_index = True
-            bracket_layers += 1
-            continue
-        elif t.type == TokenType.R_SQUARE:
-            bracket_layers -= 1
-            if bracket_layers == 0:
-                add_to_var_assignment_index = False
-                var_assignment_index.append([])
-            continue
-        elif (
-            bracket_layers == 0 and t.type == TokenType.EQUAL
-        ):  # exit when hitting the equals
-            break
-
-        if add_to_var_assignment_index:
-            var_assignment_index[-1].append(t)
-    var_assignment_index.pop()  # the last one will always be empty
+    var_assignment_index = (
+        assignment_indexes(tokens) if can_be_var_assignment else []
+    )
 
     # checking modifiers and lifetime for varianle declaration
     names_in_row: list[Token] = []
@@ -778,6 +1195,10 @@ def create_unscoped_code_statement(
                 debug=debug_level,
             )
         )
+    if len(tokens_no_ws) == 2 and tokens_no_ws[0].type == TokenType.NAME:
+        possibilities.append(
+            DebugStatement(keyword=tokens_no_ws[0], debug=debug_level)
+        )
     if can_be_return:
         possibilities.append(
             ReturnStatement(
@@ -814,6 +1235,17 @@ def create_unscoped_code_statement(
                 debug=debug_level,
             )
         )
+    if can_be_export_all:
+        possibilities.append(
+            ExportStatement(
+                export_keyword=without_whitespace[0],
+                names=[],
+                to_keyword=without_whitespace[2],
+                target_file=without_whitespace[3],
+                debug=debug_level,
+                export_all=True,
+            )
+        )
     if can_be_var_declaration:
         possibilities.append(
             VariableDeclaration(
@@ -821,7 +1253,7 @@ def create_unscoped_code_statement(
                 modifiers=names_in_row[:-1],
                 lifetime=lifetime,
                 expression=tokens[
-                    tokens_is_equal.index(True) + 1 : -1
+                    tokens_is_declaration_equal.index(True) + 1 : -1
                 ],  # the end should be a puncutation
                 confidence=confidence,
                 debug=debug_level,
@@ -853,46 +1285,93 @@ def generate_syntax_tree(
         filename, code, [tokens for tokens, _ in extracted_types]
     )
     final_statements = []
+    parse_errors: list[str] = []
 
     # now we need to perform pattern matching on each list of statements
     for (original_tokens, type_annotation), tokens in zip(
         extracted_types, removed_hints
     ):
+        try:
+            final_statements.append(
+                parse_statement(filename, tokens, code, type_annotation)
+            )
+        except InterpretationError as e:
+            if not collect_parse_errors:
+                raise
+            parse_errors.append(str(e))
 
-        without_whitespace = [t for t in tokens if t.type != TokenType.WHITESPACE]
+    if parse_errors:
+        raise ParseError("\n\n".join(parse_errors))
+    return final_statements
 
-        try:
-            # contains an open scope :)
-            if any(t.type == TokenType.L_CURLY for t in tokens):
-                final_statements.append(
-                    create_scoped_code_statement(
-                        filename, tokens, without_whitespace, code, type_annotation
-                    )
-                )
 
-            else:
-                final_statements.append(
-                    create_unscoped_code_statement(
-                        filename,
-                        tokens,
-                        without_whitespace,
-                        code,
-                        type_annotation,
-                    )
-                )
+def parse_next_statement(
+    filename: str, tokens: list[Token], code: str, position: int
+) -> Optional[tuple[tuple[CodeStatement, ...], int]]:
+    """Parse the statement starting at tokens[position].
 
-            # exit if some possiblity was found
-            if final_statements[-1]:
-                continue
-        except (
-            IndexError
-        ):  # i have no idea what kind of errors are going to be rasied here
-            pass
-        raise_error_at_line(
-            filename,
-            code,
-            without_whitespace[0].line,
-            "Error parsing statement. I have no idea what went wrong, double check it and try again.",
-        )
+    Returns the statement's possibilities and the position of the statement after
+    it, or None once the tokens run out. Indentation is not checked here.
+    """
+    statement_tokens, position = next_statement_tokens(tokens, position)
+    if not statement_tokens:
+        return None
+    [(_, type_annotation)] = extract_type_annotations(
+        filename, code, [statement_tokens]
+    )
+    [without_hints] = remove_type_hints(filename, code, [statement_tokens])
+    return parse_statement(filename, without_hints, code, type_annotation), position
 
-    return final_statements
+
+def iter_syntax_tree(
+    filename: str, tokens: list[Token], code: str
+) -> Iterator[tuple[CodeStatement, ...]]:
+    """Like generate_syntax_tree, but parses each statement only when it is needed.
+
+    Lets the interpreter run a statement before the rest of the file is parsed, so
+    only one statement's tree is held at a time. A parse error surfaces when that
+    statement is reached, after the ones before it have run.
+    """
+    assert_proper_indentation(filename, tokens, code)
+    position = 0
+    while parsed := parse_next_statement(filename, tokens, code, position):
+        statement, position = parsed
+        yield statement
+
+
+def parse_statement(
+    filename: str,
+    tokens: list[Token],
+    code: str,
+    type_annotation: Optional[list[Token]],
+) -> tuple[CodeStatement, ...]:
+    """Parse a single statement into every statement type it could be."""
+
+    without_whitespace = [t for t in tokens if t.type != TokenType.WHITESPACE]
+    try:
+        # contains an open scope :)
+        if any(t.type == TokenType.L_CURLY for t in tokens):
+            possibilities = create_scoped_code_statement(
+                filename, tokens, without_whitespace, code, type_annotation
+            )
+        else:
+            possibilities = create_unscoped_code_statement(
+                filename,
+                tokens,
+                without_whitespace,
+                code,
+                type_annotation,
+            )
+
+        # exit if some possiblity was found
+        if possibilities:
+            return possibilities
+    except IndexError:  # i have no idea what kind of errors are going to be rasied here
+        pass
+    raise_error_at_line(
+        filename,
+        code,
+        without_whitespace[0].line,
+        "Error parsing statement. I have no idea what went wrong, double check it and try again.",
+        error_type=ParseError,
+    )
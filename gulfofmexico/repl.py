@@ -6,29 +6,39 @@ Features:
     interpret_code_statements_main_wrapper
 - Persistent state across inputs (namespaces, watchers, globals)
 - Multi-line input with automatic continuation until code parses
+- Results colored by type (pass --no-color to turn colors off)
 - Commands: :help, :quit, :reset, :load <file>, :vars, :history,
-    :save <file> [all|last|<n>], :open <file>, :run <n>, :clip [last|<n>]
+    :save <file> [all|last|<n>|decls], :open <file>, :run <n>,
+    :clip [last|<n>]
 
 This REPL intentionally avoids the experimental engine; it uses the
 monolithic production interpreter in gulfofmexico/interpreter.py.
 """
 
 from __future__ import annotations
+import argparse
 import sys
 from pathlib import Path
 from typing import Optional, Union
-import re
 
+from gulfofmexico import seed_globals, split_file_sections
 from gulfofmexico.builtin import (
     KEYWORDS,
     Name,
     GulfOfMexicoValue,
     Variable,
+    GulfOfMexicoBoolean,
+    GulfOfMexicoList,
+    GulfOfMexicoNumber,
+    GulfOfMexicoString,
     GulfOfMexicoUndefined,
+    db_to_string,
+    display_string,
 )
 from gulfofmexico.processor.lexer import tokenize
-from gulfofmexico.processor.syntax_tree import generate_syntax_tree
-from gulfofmexico.base import InterpretationError
+from gulfofmexico.serialize import value_to_source
+from gulfofmexico.processor.syntax_tree import CodeStatement, generate_syntax_tree
+from gulfofmexico.base import InterpretationError, ParseError
 import gulfofmexico.interpreter as interpreter
 
 
@@ -36,11 +46,61 @@ PRIMARY_PROMPT = "gom> "
 CONT_PROMPT = " ...> "
 REPL_FILENAME = "__repl__"
 
+# A parsed file section: its name, its source, and its statements
+ParsedSection = tuple[str, str, list[tuple[CodeStatement, ...]]]
+
+RED = "\x1b[31m"
+GREEN = "\x1b[32m"
+YELLOW = "\x1b[33m"
+MAGENTA = "\x1b[35m"
+CYAN = "\x1b[36m"
+DIM = "\x1b[2m"
+RESET = "\x1b[0m"
+
+
+def paint(text: str, color: str) -> str:
+    return f"{color}{text}{RESET}"
+
+
+def format_repl_value(value: GulfOfMexicoValue, color: bool = True) -> str:
+    """Format a result for display, coloring it by type unless color is off.
+
+    Strings are quoted so they can be told apart from numbers and keywords;
+    lists format their items the same way. Everything else uses display_string.
+    """
+    match value:
+        case GulfOfMexicoString():
+            text, shade = f'"{value.value}"', GREEN
+        case GulfOfMexicoNumber():
+            text, shade = display_string(value), CYAN
+        case GulfOfMexicoBoolean():
+            text = db_to_string(value).value
+            shade = YELLOW if value.value is None else MAGENTA
+        case GulfOfMexicoUndefined():
+            text, shade = "undefined", DIM
+        case GulfOfMexicoList():
+            items = ", ".join(format_repl_value(v, color) for v in value.values)
+            return f"[{items}]"
+        case _:
+            return display_string(value)
+    return paint(text, shade) if color else text
+
+
+def declaration_source(var: Variable) -> Optional[str]:
+    """Render a variable as a declaration statement, keeping its mutability."""
+    if not var.lifetimes or (source := value_to_source(var.value)) is None:
+        return None
+    reset = "var" if var.can_be_reset else "const"
+    edit = "var" if var.can_edit_value else "const"
+    return f"{reset} {edit} {var.name} = {source}!"
+
 
 class GomRepl:
     """Stateful REPL runner bound to the production interpreter."""
 
-    def __init__(self) -> None:
+    def __init__(self, color: bool = True) -> None:
+        # Whether results and errors are printed with ANSI colors
+        self.color = color
         # Shared state across inputs
         # Namespaces: first element is a copy of keyword namespace
         self.namespaces: list[dict[str, Union[Variable, Name]]] = [
@@ -55,9 +115,12 @@ class GomRepl:
         self.history: list[str] = []
         # Optional prefilled buffer to seed the next input block
         self.prefill_lines: list[str] = []
+        # Parsed :load files by path, with the modification time they were read at
+        self.parse_cache: dict[Path, tuple[int, list[ParsedSection]]] = {}
 
         # Basic interpreter environment setup
         sys.setrecursionlimit(100000)
+        interpreter.pause_on_debug = sys.stdin.isatty()
 
         # Load global, public, and runtime globals into namespaces
         # We use an empty code block for initialization
@@ -75,6 +138,13 @@ class GomRepl:
         interpreter.load_global_gulfofmexico_variables(self.namespaces)
         interpreter.load_public_global_variables(self.namespaces)
 
+    def set_global(self, name: str, value: GulfOfMexicoValue) -> None:
+        """Make value readable as name in every later input."""
+        seed_globals(self.namespaces, {name: value})
+
+    def _paint(self, text: str, color: str) -> str:
+        return paint(text, color) if self.color else text
+
     def banner(self) -> str:
         return (
             "Gulf of Mexico REPL (production interpreter)\n"
@@ -121,7 +191,7 @@ class GomRepl:
                 )
                 # If parse succeeds, return buffer
                 return candidate
-            except InterpretationError as e:
+            except ParseError as e:
                 # Heuristic: if likely incomplete input, continue
                 # Check for common trailing characters or unmatched braces
                 open_braces = candidate.count("{") - candidate.count("}")
@@ -133,7 +203,11 @@ class GomRepl:
                     prompt = CONT_PROMPT
                     continue
                 # Otherwise, show error and reset buffer
-                print(f"\x1b[31m{e}\x1b[0m")
+                print(self._paint(str(e), RED))
+                return ""
+            except InterpretationError as e:
+                # not a syntax problem, so more input would not help
+                print(self._paint(str(e), RED))
                 return ""
 
     def _cmd_help(self) -> None:
@@ -147,6 +221,7 @@ class GomRepl:
                     ":vars              List current variables",
                     ":history [n]       Show history (list or full block n)",
                     ":save <file> [all|last|<n>]  Save history (default: all)",
+                    ":save <file> decls Save current variables as declarations",
                     ":open <file>       Prefill next input with file contents",
                     ":run [n|last]      Re-execute a history block",
                     "                   (no arg = last)",
@@ -173,36 +248,67 @@ class GomRepl:
             current = v.value.value if hasattr(v.value, "value") else v.value
             print(f"{k} = {current}")
 
+    def _cmd_save_decls(self, target: str) -> None:
+        top = self.namespaces[-1] if self.namespaces else {}
+        lines = []
+        for name, v in top.items():
+            if not isinstance(v, Variable):
+                continue
+            if (line := declaration_source(v)) is None:
+                lines.append(f"// {name}: value cannot be written as source")
+            else:
+                lines.append(line)
+        try:
+            Path(target).expanduser().write_text(
+                "".join(f"{line}\n" for line in lines), encoding="utf-8"
+            )
+            print(f"Saved {len(lines)} declaration(s) to {target}")
+        except OSError as e:
+            print(f"Failed to save to {target}: {e}")
+
+    def _parse_file(self, file: Path) -> list[ParsedSection]:
+        """Read and parse each ===== section of a file (same split as run_file).
+
+        The result is reused by later loads until the file's modification
+        time changes, so loading the same file again skips reading and parsing.
+        """
+        key = file.resolve()
+        mtime = key.stat().st_mtime_ns
+        if (cached := self.parse_cache.get(key)) is not None and cached[0] == mtime:
+            return cached[1]
+        code = file.read_text(encoding="utf-8")
+        sections: list[ParsedSection] = []
+        for section_name, section_code in split_file_sections(
+            code.splitlines(keepends=True)
+        ):
+            fname = section_name or "__unnamed_file__"
+            interpreter.filename = fname
+            interpreter.code = section_code
+            tokens = tokenize(fname, section_code)
+            statements = generate_syntax_tree(fname, tokens, section_code)
+            sections.append((fname, section_code, statements))
+        self.parse_cache[key] = (mtime, sections)
+        return sections
+
     def _cmd_load(self, path: str) -> None:
         file = Path(path).expanduser()
         if not file.exists():
             print(f"No such file: {file}")
             return
         try:
-            code = file.read_text(encoding="utf-8")
+            sections = self._parse_file(file)
         except OSError as e:
             print(f"Failed to read {file}: {e}")
             return
-        # Support multi-file sections using ===== markers (same as run_file)
-        code_lines = code.splitlines(keepends=True)
-        files: list[tuple[Optional[str], str]] = []
-        matches = [re.match(r"=====.*", l) for l in code_lines]
-        if any(matches):
-            for i, match in reversed([*enumerate(matches)]):
-                if match is None:
-                    continue
-                name = match.group().strip("=").strip() or None
-                files.insert(0, (name, "".join(code_lines[i + 1 :])))
-                del code_lines[i:]
-            files.insert(0, (None, "".join(code_lines[0:])))
-        else:
-            files = [(None, "".join(code_lines))]
+        except InterpretationError as e:
+            print(self._paint(str(e), RED))
+            self.history.append(f":load {str(file)}")
+            return
 
         # Execute each section in current REPL state, preserving namespaces
         # and handling import/export map across sections.
         any_error = False
-        for section_name, section_code in files:
-            fname = section_name or "__unnamed_file__"
+        for fname, section_code, statements in sections:
             exported_names: list[tuple[str, str, GulfOfMexicoValue]] = []
 
             # Prepare interpreter module state for this section
@@ -210,8 +316,6 @@ class GomRepl:
             interpreter.code = section_code
 
             try:
-                tokens = tokenize(fname, section_code)
-                statements = generate_syntax_tree(fname, tokens, section_code)
                 interpreter.interpret_code_statements_main_wrapper(
                     statements,
                     self.namespaces,
@@ -221,7 +325,7 @@ class GomRepl:
                     exported_names,
                 )
             except InterpretationError as e:
-                print(f"\x1b[31m{e}\x1b[0m")
+                print(self._paint(str(e), RED))
                 any_error = True
                 break
 
@@ -283,12 +387,15 @@ class GomRepl:
                 print(f"{i:>3}: {preview}")
             return True
         if op == ":save":
-            # :save <file> [all|last|<n>]
+            # :save <file> [all|last|<n>|decls]
             if len(parts) < 2:
-                print("Usage: :save <file> [all|last|<n>]")
+                print("Usage: :save <file> [all|last|<n>|decls]")
                 return True
             target = parts[1]
             mode = parts[2] if len(parts) >= 3 else "all"
+            if mode == "decls":
+                self._cmd_save_decls(target)
+                return True
             try:
                 if mode == "all":
                     content = "\n\n".join(self.history)
@@ -304,7 +411,7 @@ class GomRepl:
                 Path(target).expanduser().write_text(content, encoding="utf-8")
                 print(f"Saved to {target}")
             except ValueError:
-                print("Usage: :save <file> [all|last|<n>]")
+                print("Usage: :save <file> [all|last|<n>|decls]")
             except OSError as e:
                 print(f"Failed to save to {target}: {e}")
             return True
@@ -398,7 +505,7 @@ class GomRepl:
                 exported_names,
             )
         except InterpretationError as e:
-            print(f"\x1b[31m{e}\x1b[0m")
+            print(self._paint(str(e), RED))
             return
 
         # Handle exported names
@@ -409,8 +516,7 @@ class GomRepl:
 
         # Only print meaningful results (suppress implicit 'undefined')
         if result is not None and not isinstance(result, GulfOfMexicoUndefined):
-            # Best-effort print of result
-            print(result)
+            print(format_repl_value(result, self.color))
 
         # Record successful block in history
         self.history.append(code)
@@ -457,8 +563,12 @@ class GomRepl:
 
 
 def main(argv: list[str] | None = None) -> int:
-    _ = argv or sys.argv[1:]
-    repl = GomRepl()
+    parser = argparse.ArgumentParser(prog="gulfofmexico.repl")
+    parser.add_argument(
+        "--no-color", action="store_true", help="print results without colors"
+    )
+    ns = parser.parse_args(argv if argv is not None else sys.argv[1:])
+    repl = GomRepl(color=not ns.no_color)
     repl.loop()
     return 0
 
@@ -21,13 +21,15 @@ Supported Types:
 Usage:
     - serialize_obj(value) -> dict: Convert to JSON-serializable dict
     - deserialize_obj(dict) -> value: Reconstruct from serialized dict
+    - value_to_source(value) -> str: Gulf of Mexico literal for the value, if any
 
 Note: Used by export/import statements and const const const global storage.
 """
 
 import json
 import dataclasses
-from typing import Any, Callable, Type, Union, assert_never
+import math
+from typing import Any, Callable, Optional, Type, Union, assert_never
 from gulfofmexico.base import NonFormattedError, Token, TokenType
 
 from gulfofmexico.builtin import *
@@ -40,17 +42,29 @@ from gulfofmexico.builtin import (
     Name,
     GulfOfMexicoValue,
     Variable,
+    VariableLifetime,
+    EXACT_INT_LIMIT,
+    db_to_string,
 )
 from gulfofmexico.processor.syntax_tree import CodeStatement
 
 SerializedDict = dict[str, Union[str, dict, list]]
-DataclassSerializations = Union[Name, Variable, GulfOfMexicoValue, CodeStatement, Token]
+DataclassSerializations = Union[
+    Name, Variable, VariableLifetime, GulfOfMexicoValue, CodeStatement, Token
+]
 
 
 def serialize_obj(obj: Any) -> SerializedDict:
     """Convert Gulf of Mexico or Python object to JSON-serializable dict."""
     match obj:
-        case Name() | Variable() | GulfOfMexicoValue() | CodeStatement() | Token():
+        case (
+            Name()
+            | Variable()
+            | VariableLifetime()
+            | GulfOfMexicoValue()
+            | CodeStatement()
+            | Token()
+        ):
             return serialize_gulfofmexico_obj(obj)
         case _:
             return serialize_python_obj(obj)
@@ -71,12 +85,15 @@ def serialize_python_obj(obj: Any) -> dict[str, Union[str, dict, list]]:
     match obj:
         case TokenType():
             val = obj.value
+        case dict() if all(isinstance(k, str) for k in obj):
+            val = {k: serialize_obj(v) for k, v in obj.items()}
         case dict():
-            if not all(isinstance(k, str) for k in obj):
+            # JSON objects only have string keys, so keep number keys as pairs
+            if not all(isinstance(k, (str, int, float)) for k in obj):
                 raise NonFormattedError(
-                    "Serialization Error: Encountered non-string dictionary keys."
+                    "Serialization Error: Encountered unsupported dictionary keys."
                 )
-            val = {k: serialize_obj(v) for k, v in obj.items()}
+            val = [[serialize_obj(k), serialize_obj(v)] for k, v in obj.items()]
         case list() | tuple():
             val = [serialize_obj(x) for x in obj]
         case str():
@@ -118,6 +135,8 @@ def deserialize_python_obj(val: dict) -> Any:
             return [deserialize_obj(x) for x in val["value"]]
         case "tuple":
             return tuple(deserialize_obj(x) for x in val["value"])
+        case "dict" if isinstance(val["value"], list):
+            return {deserialize_obj(k): deserialize_obj(v) for k, v in val["value"]}
         case "dict":
             return {k: deserialize_obj(v) for k, v in val["value"].items()}
         case "int" | "float" | "str":
@@ -163,6 +182,7 @@ def serialize_gulfofmexico_obj(
         "attributes": [
             {"name": field.name, "value": serialize_obj(getattr(val, field.name))}
             for field in dataclasses.fields(val)  # type: ignore
+            if field.init  # derived fields (list/string indexers) are rebuilt on init
         ],
     }
 
@@ -175,6 +195,7 @@ def deserialize_gulfofmexico_obj(val: dict) -> DataclassSerializations:
     if val["gulfofmexico_obj_type"] not in [
         "Name",
         "Variable",
+        "VariableLifetime",
         "Token",
         *get_subclass_name_list(CodeStatement),
         *get_subclass_name_list(GulfOfMexicoValue),
@@ -188,6 +209,41 @@ def deserialize_gulfofmexico_obj(val: dict) -> DataclassSerializations:
     return eval(val["gulfofmexico_obj_type"])(**attrs)
 
 
+def number_to_source(number: Union[int, float]) -> Optional[str]:
+    """A literal that reads back as exactly number, unlike the printed form."""
+    if isinstance(number, int):
+        return str(number)
+    if not math.isfinite(number):
+        return None
+    if number.is_integer() and abs(number) < EXACT_INT_LIMIT:
+        return str(int(number))
+    return repr(number)  # 1e-09 and 1e+300 lex as exponent literals
+
+
+def value_to_source(value: GulfOfMexicoValue) -> Optional[str]:
+    """Render a value as a literal that evaluates back to it, if possible.
+
+    Functions, objects and maps have no literal form, and strings that would
+    need escaping (quotes, backslashes, ${} or newlines) are not written either.
+    Neither are inf and nan.
+    """
+    match value:
+        case GulfOfMexicoNumber():
+            return number_to_source(value.value)
+        case GulfOfMexicoBoolean() | GulfOfMexicoUndefined():
+            return db_to_string(value).value
+        case GulfOfMexicoString():
+            if any(c in value.value for c in "\"'\n\\") or "${" in value.value:
+                return None
+            return f'"{value.value}"'
+        case GulfOfMexicoList():
+            items = [value_to_source(v) for v in value.values]
+            if any(item is None for item in items):
+                return None
+            return f"[{', '.join(items)}]"  # type: ignore
+    return None
+
+
 if __name__ == "__main__":
 
     list_test_case = GulfOfMexicoList(
@@ -0,0 +1,65 @@
+"""Shared helpers for tests that exercise the production interpreter.
+
+Unlike the handler tests, these run real Gulf of Mexico source through
+tokenize → generate_syntax_tree → interpret_code_statements_main_wrapper,
+the same path used by run_file() and the REPL.
+"""
+
+import io
+from contextlib import redirect_stderr, redirect_stdout
+from typing import Optional
+
+import gulfofmexico.interpreter as interpreter
+from gulfofmexico.builtin import KEYWORDS
+from gulfofmexico.processor.lexer import tokenize
+from gulfofmexico.processor.syntax_tree import generate_syntax_tree
+
+
+def run_code(
+    code: str,
+    filename: str = "__test__",
+    importable_names: Optional[dict] = None,
+    exported_names: Optional[list] = None,
+    stderr: Optional[io.StringIO] = None,
+) -> str:
+    """Execute a snippet with fresh interpreter state and return its stdout.
+
+    Global variables (local runtime and GitHub) are intentionally not loaded
+    so tests stay hermetic. Pass importable_names / exported_names to stand in
+    for the other file sections that run_file() would wire up. Pass a StringIO
+    as stderr to inspect warnings; otherwise stderr is discarded.
+    """
+    interpreter.filename = filename
+    interpreter.code = code
+    interpreter.current_line = 0
+    interpreter.deleted_values.clear()
+    interpreter.name_watchers.clear()
+    interpreter.when_handles.clear()
+    interpreter.stopped_when_conditions.clear()
+    interpreter.after_event_handlers.clear()
+    interpreter.scheduled_afters.clear()
+    interpreter.expiring_variables.clear()
+
+    tokens = tokenize(filename, code)
+    statements = generate_syntax_tree(filename, tokens, code)
+    stdout = io.StringIO()
+    with redirect_stdout(stdout), redirect_stderr(stderr or io.StringIO()):
+        interpreter.interpret_code_statements_main_wrapper(
+            statements,
+            [KEYWORDS.copy()],  # type: ignore
+            [],
+            [{}],
+            importable_names if importable_names is not None else {},
+            exported_names if exported_names is not None else [],
+        )
+    return stdout.getvalue()
+
+
+def output_lines(code: str, **kwargs) -> list[str]:
+    """Like run_code(), but split into lines for easier assertions."""
+    return run_code(code, **kwargs).splitlines()
+
+
+def value_of(expression: str, setup: str = "") -> str:
+    """Run setup, then print expression, returning the line it prints."""
+    return output_lines(f"{setup}const v = {expression}!\nprint(v)!\n")[-1]
@@ -0,0 +1,111 @@
+"""Tests for using the gulfofmexico package as a library."""
+
+import io
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+
+import gulfofmexico
+import gulfofmexico.interpreter as interpreter
+from gulfofmexico.base import InterpretationError
+from gulfofmexico.builtin import GulfOfMexicoNumber, GulfOfMexicoString
+
+
+class TestLibraryApi(unittest.TestCase):
+    def setUp(self):
+        interpreter.load_persisted_globals = False
+
+    def tearDown(self):
+        interpreter.load_persisted_globals = True
+
+    def run_source(self, code: str, **kwargs) -> str:
+        stdout = io.StringIO()
+        with redirect_stdout(stdout), redirect_stderr(io.StringIO()):
+            gulfofmexico.run_source(code, **kwargs)
+        return stdout.getvalue()
+
+    def test_run_source(self):
+        output = self.run_source(
+            'function greet(name) => "hi " + name!\n'
+            'const const who = "there"!\n'
+            "print(greet(who))!\n"
+        )
+        self.assertEqual(output, "hi there\n")
+
+    def test_run_source_returns_last_value(self):
+        with redirect_stderr(io.StringIO()):
+            value = gulfofmexico.run_source("const const x = 1!\nx + 2!\n")
+        self.assertEqual(value, GulfOfMexicoNumber(3))
+
+    def test_reading_results_with_value_api(self):
+        with redirect_stderr(io.StringIO()):
+            number = gulfofmexico.run_source("const const x = 1!\nx + 2!\n")
+            text = gulfofmexico.run_source('"hi"!\n')
+        self.assertEqual(number.as_number(), 3)
+        self.assertIsNone(number.as_string())
+        self.assertEqual(text.as_string(), "hi")
+
+    def test_run_source_with_variables(self):
+        output = self.run_source(
+            'print(greeting)!\nprint(limit*2)!\n',
+            variables={
+                "greeting": GulfOfMexicoString("hi"),
+                "limit": GulfOfMexicoNumber(5),
+            },
+        )
+        self.assertEqual(output, "hi\n10\n")
+
+    def test_script_can_shadow_variables(self):
+        output = self.run_source(
+            "const const limit = 3!\nprint(limit)!\n",
+            variables={"limit": GulfOfMexicoNumber(5)},
+        )
+        self.assertEqual(output, "3\n")
+
+    def test_run_source_with_sections(self):
+        output = self.run_source(
+            'const const x = 5!\nexport x to "lib"!\n'
+            "===== lib =====\n"
+            "import x!\nprint(x)!\n"
+        )
+        self.assertEqual(output, "5\n")
+
+    def test_output_streams(self):
+        stdout, stderr = io.StringIO(), io.StringIO()
+        gulfofmexico.run_source("print(1)!\n", stdout=stdout, stderr=stderr)
+        self.assertEqual(stdout.getvalue(), "1\n")
+        self.assertIn("[DB_PRINT]", stderr.getvalue())
+
+    def test_run_capturing(self):
+        code = "".join(f"print({i})!\n" for i in range(50)) + "const const x = 7!\nx!\n"
+        real_stdout = io.StringIO()
+        with redirect_stdout(real_stdout):
+            run = gulfofmexico.run_capturing(code)
+        self.assertEqual(real_stdout.getvalue(), "")
+        self.assertEqual(run.stdout, "".join(f"{i}\n" for i in range(50)))
+        self.assertEqual(run.stderr.count("[DB_PRINT]"), 50)
+        self.assertEqual(run.value, GulfOfMexicoNumber(7))
+        self.assertIsNone(run.error)
+
+    def test_run_capturing_keeps_output_before_an_error(self):
+        run = gulfofmexico.run_capturing('print("before")!\nprint(missing)!\n')
+        self.assertEqual(run.stdout, "before\n")
+        self.assertIsInstance(run.error, InterpretationError)
+        self.assertIsNone(run.value)
+
+    def test_filename_appears_in_errors(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            self.run_source("print(missing)!\n", filename="embedded.gom")
+        self.assertIn("embedded.gom", str(ctx.exception))
+
+    def test_parsing_api(self):
+        code = "const const x = 1!\nprint(x)!\n"
+        tokens = gulfofmexico.tokenize("__test__", code)
+        statements = gulfofmexico.generate_syntax_tree("__test__", tokens, code)
+        self.assertEqual(len(statements), 2)
+        self.assertEqual(
+            list(gulfofmexico.iter_syntax_tree("__test__", tokens, code)), statements
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
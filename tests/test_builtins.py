@@ -0,0 +1,642 @@
+"""Tests for built-in functions, run through the production interpreter."""
+
+import os
+import tempfile
+import unittest
+from pathlib import Path
+from unittest.mock import patch
+
+from gulfofmexico.builtin import (
+    GulfOfMexicoBoolean,
+    GulfOfMexicoList,
+    GulfOfMexicoNumber,
+    GulfOfMexicoString,
+    GulfOfMexicoValue,
+    KEYWORDS,
+    db_to_boolean,
+    db_to_number,
+    db_to_string,
+)
+import gulfofmexico.builtin as builtin
+import gulfofmexico.interpreter as interpreter
+from gulfofmexico.base import InterpretationError
+from gulfofmexico.serialize import value_to_source
+from helpers import output_lines, run_code, value_of
+
+
+class TestLineCount(unittest.TestCase):
+    def test_line_count_increases_across_statements(self):
+        lines = output_lines(
+            "const first = line_count()!\n"
+            "const second = line_count()!\n"
+            "\n"
+            "const third = line_count()!\n"
+            "print(first)!\n"
+            "print(second)!\n"
+            "print(third)!\n"
+        )
+        first, second, third = (int(v) for v in lines)
+        self.assertLess(first, second)
+        self.assertLess(second, third)
+        self.assertEqual(third, 4)
+
+
+class TestLifetime(unittest.TestCase):
+    def test_line_lifetime_counts_down(self):
+        lines = output_lines(
+            "const const x<5> = 1!\n"
+            'print(lifetime("x"))!\n'
+            "const const pad = 0!\n"
+            'print(lifetime("x"))!\n'
+            'print(lifetime("x"))!\n'
+        )
+        self.assertEqual(lines, ["4", "2", "1"])
+
+    def test_expired_variable_has_no_lifetime(self):
+        lines = output_lines('const const x<1> = 1!\n\n\nprint(lifetime("x"))!\n')
+        self.assertEqual(lines, ["undefined"])
+
+    def test_reassignment_restarts_lifetime(self):
+        lines = output_lines(
+            "var var x<3> = 1!\n"
+            "const const pad = 0!\n"
+            "x = 2!\n"
+            'print(lifetime("x"))!\n'
+            "print(x)!\n"
+            'print(lifetime("x"))!\n'
+        )
+        # without the refresh, x would have expired on line 4; with it, on line 6
+        self.assertEqual(lines, ["2", "2", "undefined"])
+
+    def test_infinite_and_unknown_names_are_undefined(self):
+        lines = output_lines(
+            "const const y = 2!\n"
+            'print(lifetime("y"))!\n'
+            'print(lifetime("nope"))!\n'
+            'print(lifetime("print"))!\n'
+        )
+        self.assertEqual(lines, ["undefined"] * 3)
+
+
+class TestBuiltinsListing(unittest.TestCase):
+    def listing(self) -> dict:
+        pairs = KEYWORDS["builtins"].value.function([KEYWORDS.copy()])
+        return {p.values[0].value: p.values[1].value for p in pairs.values}
+
+    def test_known_builtins_are_listed(self):
+        listing = self.listing()
+        self.assertEqual(listing["print"], -1)
+        self.assertEqual(listing["len"], 1)
+        self.assertIn("sqrt", listing)
+        self.assertNotIn("true", listing)
+
+    def test_callable_from_code(self):
+        (line,) = output_lines("const const b = builtins()!\nprint(b)!\n")
+        self.assertIn("[len, 1]", line)
+        self.assertIn("[print, -1]", line)
+
+
+class TestRestoreBuiltin(unittest.TestCase):
+    OVERRIDE = (
+        "const const original = print!\n"
+        'function shout(x) => original("SHOUT")!\n'
+        "const const print = shout!\n"
+    )
+
+    def test_override_print(self):
+        lines = output_lines(self.OVERRIDE + 'print("hi")!\n')
+        self.assertEqual(lines, ["SHOUT"])
+
+    def test_restore_print(self):
+        lines = output_lines(
+            self.OVERRIDE + 'print("hi")!\nrestore_builtin("print")!\nprint("hi")!\n'
+        )
+        self.assertEqual(lines, ["SHOUT", "hi"])
+
+    def test_unknown_builtin(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            output_lines('restore_builtin("nope")!\n')
+        self.assertIn("no builtin named nope", str(ctx.exception))
+
+
+class TestEval(unittest.TestCase):
+    def tearDown(self):
+        interpreter.max_statements = None
+
+    def test_returns_last_value(self):
+        self.assertEqual(output_lines('print(eval("1 + 1!"))!\n'), ["2"])
+
+    def test_declarations_are_visible_afterwards(self):
+        lines = output_lines('eval("const const y = 5!")!\nprint(y)!\n')
+        self.assertEqual(lines, ["5"])
+
+    def test_runs_in_the_callers_scope(self):
+        lines = output_lines('function f(n) => eval("n * 3!")!\nprint(f(4))!\n')
+        self.assertEqual(lines, ["12"])
+
+    def test_errors_point_into_the_evaluated_code(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            output_lines('const const x = 1!\neval("print(nope)!")!\n')
+        self.assertIn("(eval), line 1", str(ctx.exception))
+        self.assertIn("print(nope)!", str(ctx.exception))
+
+    def test_self_evaluation_stops_at_statement_budget(self):
+        interpreter.max_statements = 200
+        with self.assertRaises(InterpretationError) as ctx:
+            output_lines('function f() => eval("f()!")!\nf()!\n')
+        self.assertIn("Statement budget exceeded", str(ctx.exception))
+
+
+class TestImportValue(unittest.TestCase):
+    def setUp(self):
+        interpreter.load_persisted_globals = False
+        self.tmp = tempfile.TemporaryDirectory()
+        self.lib = Path(self.tmp.name) / "lib.gom"
+
+    def tearDown(self):
+        interpreter.load_persisted_globals = True
+        self.tmp.cleanup()
+
+    def test_returns_last_expression(self):
+        self.lib.write_text("42!\n", encoding="utf-8")
+        lines = output_lines(f'print(import_value("{self.lib}"))!\n')
+        self.assertEqual(lines, ["42"])
+
+    def test_declarations_stay_in_the_sandbox(self):
+        self.lib.write_text("const const secret = 40!\nsecret + 2!\n", encoding="utf-8")
+        with self.assertRaises(InterpretationError) as ctx:
+            output_lines(
+                f'const const v = import_value("{self.lib}")!\n'
+                "print(v)!\n"
+                "print(secret)!\n"
+            )
+        # the error is reported against the caller's code, not the imported file
+        self.assertIn("__test__, line 3", str(ctx.exception))
+        self.assertIn("Undefined name: secret", str(ctx.exception))
+
+    def test_missing_file(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            output_lines(f'import_value("{self.lib}")!\n')
+        self.assertIn("Cannot read", str(ctx.exception))
+
+
+class TestStringIndexer(unittest.TestCase):
+    def test_large_string_builds_no_indexer(self):
+        s = GulfOfMexicoString("x" * 1_000_000)
+        self.assertIsNone(s._indexer)
+        self.assertEqual(s, GulfOfMexicoString("x" * 1_000_000))
+
+    def test_indexing_after_lazy_init(self):
+        s = GulfOfMexicoString("abc")
+        self.assertEqual(s.access_index(GulfOfMexicoNumber(0)), "b")
+        self.assertEqual(len(s.indexer), 3)
+        lines = output_lines(
+            'var var s = "abc"!\n'
+            "print(s[-1])!\n"
+            's[0.5] = "x"!\n'
+            "print(s)!\n"
+            "print(s[1])!\n"
+            's.push("d")!\n'
+            "print(s)!\n"
+        )
+        self.assertEqual(lines, ["a", "abxc", "c", "abxcd"])
+
+
+class TestFormat(unittest.TestCase):
+    def test_basic_substitution(self):
+        lines = output_lines(
+            'const name = "Ann"!\n'
+            'const s = format("Hi {0}, you are {1}", name, 3)!\n'
+            "print(s)!\n"
+        )
+        self.assertEqual(lines, ["Hi Ann, you are 3"])
+
+    def test_repeated_indices(self):
+        lines = output_lines('const s = format("{0}{0}-{1}", "ab", "c")!\nprint(s)!\n')
+        self.assertEqual(lines, ["abab-c"])
+
+    def test_escaping_and_out_of_range(self):
+        lines = output_lines('const s = format("{{0}} {0} {5}", 1)!\nprint(s)!\n')
+        self.assertEqual(lines, ["{0} 1 {5}"])
+
+
+class TestStringFunctions(unittest.TestCase):
+    def test_starts_and_ends_with(self):
+        lines = output_lines(
+            'const s = "hello"!\n'
+            'const a = starts_with(s, "he")!\nconst b = ends_with(s, "he")!\n'
+            "print(a)!\nprint(b)!\n"
+        )
+        self.assertEqual(lines, ["true", "false"])
+
+    def test_coerces_non_strings(self):
+        lines = output_lines("const a = starts_with(123, 12)!\nprint(a)!\n")
+        self.assertEqual(lines, ["true"])
+
+    def test_trim(self):
+        lines = output_lines('const t = trim("  hi  ")!\nprint(t+"|")!\n')
+        self.assertEqual(lines, ["hi|"])
+
+    def test_trim_only_whitespace(self):
+        lines = output_lines('const t = trim("    ")!\nprint(len(t))!\n')
+        self.assertEqual(lines, ["0"])
+
+    def test_replace_all(self):
+        lines = output_lines('const r = replace("a-b-c", "-", "+")!\nprint(r)!\n')
+        self.assertEqual(lines, ["a+b+c"])
+
+    def test_replace_empty_needle(self):
+        lines = output_lines('const r = replace("abc", "", "x")!\nprint(r)!\n')
+        self.assertEqual(lines, ["abc"])
+
+
+class TestCharacters(unittest.TestCase):
+    def test_char_at_uses_string_indexing(self):
+        lines = output_lines(
+            'const s = "abc"!\nconst a = char_at(s, -1)!\nconst b = char_at(s, 1)!\n'
+            "print(a)!\nprint(b)!\n"
+        )
+        self.assertEqual(lines, ["a", "c"])
+
+    def test_char_at_out_of_range_is_undefined(self):
+        lines = output_lines('const c = char_at("abc", 5)!\nprint(c)!\n')
+        self.assertEqual(lines, ["undefined"])
+
+    def test_char_code_round_trip(self):
+        lines = output_lines(
+            'const n = char_code("A")!\nconst c = from_char_code(n)!\n'
+            'const same = c=="A"!\nprint(n)!\nprint(same)!\n'
+        )
+        self.assertEqual(lines, ["65", "true"])
+
+    def test_invalid_code_point(self):
+        with self.assertRaises(InterpretationError):
+            output_lines("const c = from_char_code(-5)!\n")
+
+
+class TestRange(unittest.TestCase):
+    def range_of(self, args: str) -> str:
+        return output_lines(f"const r = range({args})!\nprint(r)!\n")[0]
+
+    def test_end_only(self):
+        self.assertEqual(self.range_of("4"), "[0, 1, 2, 3]")
+
+    def test_start_and_end(self):
+        self.assertEqual(self.range_of("2, 5"), "[2, 3, 4]")
+
+    def test_step(self):
+        self.assertEqual(self.range_of("0, 10, 4"), "[0, 4, 8]")
+
+    def test_descending(self):
+        self.assertEqual(self.range_of("3, 0, -1"), "[3, 2, 1]")
+
+    def test_empty(self):
+        self.assertEqual(self.range_of("5, 2"), "[]")
+
+    def test_result_indexes_from_minus_one(self):
+        lines = output_lines("const r = range(10, 13)!\nprint(r[-1])!\nprint(r[1])!\n")
+        self.assertEqual(lines, ["10", "12"])
+
+
+class TestPairing(unittest.TestCase):
+    def test_enumerate_counts_from_minus_one(self):
+        lines = output_lines('const e = enumerate(["a", "b"])!\nprint(e)!\n')
+        self.assertEqual(lines, ["[[-1, a], [0, b]]"])
+
+    def test_zip_equal_lengths(self):
+        lines = output_lines(
+            'const a = [1, 2]!\nconst b = ["a", "b"]!\n'
+            "const z = zip(a, b)!\nprint(z)!\n"
+        )
+        self.assertEqual(lines, ["[[1, a], [2, b]]"])
+
+    def test_zip_truncates_to_shorter(self):
+        lines = output_lines(
+            'const a = [1, 2, 3]!\nconst b = ["a"]!\nconst z = zip(a, b)!\nprint(z)!\n'
+        )
+        self.assertEqual(lines, ["[[1, a]]"])
+
+
+class TestCoalesce(unittest.TestCase):
+    def test_undefined_uses_fallback(self):
+        lines = output_lines("const x = coalesce(undefined, 5)!\nprint(x)!\n")
+        self.assertEqual(lines, ["5"])
+
+    def test_maybe_uses_fallback(self):
+        lines = output_lines('const x = coalesce(maybe, "sure")!\nprint(x)!\n')
+        self.assertEqual(lines, ["sure"])
+
+    def test_real_value_is_kept(self):
+        lines = output_lines(
+            "const x = coalesce(3, 5)!\nprint(x)!\n"
+            "const y = coalesce(false, true)!\nprint(y)!\n"
+        )
+        self.assertEqual(lines, ["3", "false"])
+
+    def test_registered_with_the_builtins_not_math(self):
+        self.assertIn("coalesce", builtin.BUILTIN_FUNCTION_KEYWORDS)
+        self.assertNotIn("coalesce", builtin.MATH_FUNCTION_KEYWORDS)
+
+
+class TestMapBuiltins(unittest.TestCase):
+    def test_build_map_imperatively(self):
+        lines = output_lines(
+            "const const m = new Map()!\n"
+            'map_set(m, "a", 1)!\nmap_set(m, "b", 2)!\nmap_set(m, 3, "three")!\n'
+            'const const a = map_get(m, "a")!\nprint(a)!\n'
+            "const const three = map_get(m, 3)!\nprint(three)!\n"
+            "print(m)!\n"
+        )
+        self.assertEqual(lines, ["1", "three", "{a: 1, b: 2, 3: three}"])
+
+    def test_missing_key_is_undefined(self):
+        lines = output_lines('const const x = map_get(Map(), "nope")!\nprint(x)!\n')
+        self.assertEqual(lines, ["undefined"])
+
+    def test_delete_returns_old_value(self):
+        lines = output_lines(
+            'const const m = Map()!\nmap_set(m, "a", 1)!\n'
+            'const const old = map_delete(m, "a")!\nprint(old)!\n'
+            'const const gone = map_get(m, "a")!\nprint(gone)!\n'
+            'const const again = map_delete(m, "a")!\nprint(again)!\n'
+        )
+        self.assertEqual(lines, ["1", "undefined", "undefined"])
+
+    def test_non_map_is_an_error(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('map_set([1], "a", 1)!\n')
+        self.assertIn("'map_set' requires a map.", str(ctx.exception))
+
+    def test_changes_trigger_when(self):
+        lines = output_lines(
+            'const m = Map()!\nm["k"] = 0!\n'
+            'when (map_get(m, "k") == 1) {\n   print("fired")!\n}\n'
+            'm["k"] = 1!\nmap_set(m, "k", 0)!\nmap_set(m, "k", 1)!\n'
+            'when (map_get(m, "k") == undefined) {\n   print("gone")!\n}\n'
+            'map_delete(m, "k")!\n'
+        )
+        self.assertEqual(lines, ["fired", "fired", "gone"])
+
+
+class TestNumberHelpers(unittest.TestCase):
+    def test_clamp(self):
+        cases = [
+            ("clamp(2, 5, 10)", "5"),
+            ("clamp(7.5, 5, 10)", "7.5"),
+            ("clamp(12, 5, 10)", "10"),
+        ]
+        for call, expected in cases:
+            with self.subTest(call=call):
+                self.assertEqual(value_of(call), expected)
+
+    def test_sign(self):
+        cases = [("sign(7)", "1"), ("sign(-0.5)", "-1"), ("sign(0)", "0")]
+        for call, expected in cases:
+            with self.subTest(call=call):
+                self.assertEqual(value_of(call), expected)
+
+    def test_abs_diff(self):
+        self.assertEqual(value_of("abs_diff(3, 10)"), "7")
+        self.assertEqual(value_of("abs_diff(10, 3)"), "7")
+
+    def test_bad_input_is_undefined(self):
+        calls = ['clamp("a", 0, 1)', "clamp(5, 10, 0)", 'sign("a")', "abs_diff(1, [])"]
+        for call in calls:
+            with self.subTest(call=call):
+                self.assertEqual(value_of(call), "undefined")
+
+
+class TestMathFunctions(unittest.TestCase):
+    def test_known_values(self):
+        cases = [
+            ("sin(0)", 0),
+            ("cos(0)", 1),
+            ("tan(pi/4)", 1),
+            ("log(e)", 1),
+            ("log10(1000)", 3),
+            ("exp(0)", 1),
+        ]
+        for call, expected in cases:
+            with self.subTest(call=call):
+                self.assertAlmostEqual(float(value_of(call)), expected)
+
+    def test_bad_input_is_undefined(self):
+        for call in ["log(0)", "log10(-1)", "exp(100000)", 'sin("a")']:
+            with self.subTest(call=call):
+                self.assertEqual(value_of(call), "undefined")
+
+    def test_other_domain_errors_are_reported(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("const v = sqrt(-1)!\n")
+        self.assertIn("'sqrt' failed: math domain error.", str(ctx.exception))
+
+
+class TestBooleanBuiltins(unittest.TestCase):
+    VALUES = ["true", "false", "maybe"]
+    XOR = {
+        ("true", "true"): "false",
+        ("true", "false"): "true",
+        ("false", "true"): "true",
+        ("false", "false"): "false",
+    }
+    NAND = {
+        ("true", "true"): "false",
+        ("true", "maybe"): "maybe",
+        ("maybe", "true"): "maybe",
+        ("maybe", "maybe"): "maybe",
+    }
+
+    def result(self, func: str, a: str, b: str) -> str:
+        return output_lines(f"const v = {func}({a}, {b})!\nprint(v)!\n")[0]
+
+    def test_xor_truth_table(self):
+        for a in self.VALUES:
+            for b in self.VALUES:
+                with self.subTest(a=a, b=b):
+                    self.assertEqual(
+                        self.result("xor", a, b), self.XOR.get((a, b), "maybe")
+                    )
+
+    def test_nand_truth_table(self):
+        for a in self.VALUES:
+            for b in self.VALUES:
+                with self.subTest(a=a, b=b):
+                    self.assertEqual(
+                        self.result("nand", a, b), self.NAND.get((a, b), "true")
+                    )
+
+
+class TestPrintWithoutArguments(unittest.TestCase):
+    def test_empty_call_prints_one_newline(self):
+        self.assertEqual(run_code("print()!\n"), "\n")
+
+    def test_bare_print_prints_one_newline(self):
+        self.assertEqual(run_code("print!\n"), "\n")
+
+    def test_bare_print_between_lines(self):
+        output = run_code('print("a")!\nprint!\nprint("b")!\n')
+        self.assertEqual(output, "a\n\nb\n")
+
+
+class TestBuiltinEquality(unittest.TestCase):
+    def test_same_named_builtins_are_distinct(self):
+        # twenty and thirty are both closures named the_func with one argument
+        twenty, thirty = KEYWORDS["twenty"].value, KEYWORDS["thirty"].value
+        self.assertEqual(twenty.function.__name__, thirty.function.__name__)
+        self.assertFalse(interpreter.is_equal(twenty, thirty).value)
+        self.assertFalse(interpreter.is_approx_equal(twenty, thirty).value)
+        self.assertTrue(interpreter.is_equal(twenty, KEYWORDS["twenty"].value).value)
+
+
+class TestScriptArgs(unittest.TestCase):
+    def tearDown(self):
+        interpreter.script_args = []
+
+    def test_args_returns_script_arguments(self):
+        interpreter.script_args = ["one", "two words", "--three"]
+        lines = output_lines("const a = args()!\nprint(len(a))!\nprint(a)!\n")
+        self.assertEqual(lines, ["3", "[one, two words, --three]"])
+
+    def test_args_empty_by_default(self):
+        self.assertEqual(output_lines("print(len(args()))!\n"), ["0"])
+
+
+class TestEnvironment(unittest.TestCase):
+    def test_setenv_then_getenv(self):
+        with patch.dict(os.environ):
+            lines = output_lines(
+                'setenv("GOM_TEST_VAR", 42)!\nprint(getenv("GOM_TEST_VAR"))!\n'
+            )
+            self.assertEqual(os.environ["GOM_TEST_VAR"], "42")
+        self.assertEqual(lines, ["42"])
+
+    def test_getenv_unset(self):
+        with patch.dict(os.environ):
+            os.environ.pop("GOM_TEST_UNSET", None)
+            lines = output_lines('print(getenv("GOM_TEST_UNSET"))!\n')
+        self.assertEqual(lines, ["undefined"])
+
+
+class TestNumberDisplay(unittest.TestCase):
+    def show(self, value: float) -> str:
+        return db_to_string(GulfOfMexicoNumber(value)).value
+
+    def test_negative_zero(self):
+        self.assertEqual(self.show(-0.0), "0")
+
+    def test_integer_valued_floats(self):
+        self.assertEqual(self.show(-3.0), "-3")
+        self.assertEqual(self.show(3.0), "3")
+
+    def test_values_inside_tolerance_round_to_nearest(self):
+        self.assertEqual(self.show(-2.999999996), "-3")
+        self.assertEqual(self.show(-3.000000004), "-3")
+        self.assertEqual(self.show(2.999999996), "3")
+
+    def test_non_integers_keep_their_digits(self):
+        self.assertEqual(self.show(-2.5), "-2.5")
+        self.assertEqual(self.show(-2.9999), "-2.9999")
+
+    def test_tiny_and_huge_values_stay_floats(self):
+        self.assertEqual(self.show(1e-9), "1e-09")
+        self.assertEqual(self.show(-1e-9), "-1e-09")
+        self.assertEqual(self.show(1e300), "1e+300")
+        self.assertEqual(self.show(2.0**53 - 1), "9007199254740991")
+
+    def test_division_result_prints_as_integer(self):
+        self.assertEqual(output_lines("const x = 6/2!\nprint(x)!\n"), ["3"])
+
+
+class TestNumberDisplayModes(unittest.TestCase):
+    PROGRAM = "const x = 1234567!\nprint(x)!\nconst y = 0.00125!\nprint(y)!\n"
+
+    def tearDown(self):
+        builtin.number_display = "plain"
+
+    def test_plain(self):
+        self.assertEqual(output_lines(self.PROGRAM), ["1234567", "0.00125"])
+
+    def test_thousands(self):
+        builtin.number_display = "thousands"
+        self.assertEqual(output_lines(self.PROGRAM), ["1,234,567", "0.00125"])
+
+    def test_scientific(self):
+        builtin.number_display = "scientific"
+        self.assertEqual(output_lines(self.PROGRAM), ["1.234567e+06", "1.25e-03"])
+
+    def test_infinity_and_nan(self):
+        for mode in builtin.NUMBER_DISPLAY_MODES:
+            with self.subTest(mode=mode):
+                builtin.number_display = mode
+                lines = output_lines(
+                    "const y = 1e300 * 1e300!\nprint(y)!\n"
+                    "const z = 0 - y!\nprint(z)!\n"
+                )
+                self.assertEqual(lines, ["inf", "-inf"])
+                nan = GulfOfMexicoNumber(float("nan"))
+                self.assertEqual(builtin.display_string(nan), "nan")
+
+    def test_strings_stay_plain(self):
+        builtin.number_display = "thousands"
+        lines = output_lines(
+            'const s = "n" + 1000!\nprint(s)!\n'
+            "const back = Number(String(1000))!\nprint(back)!\n"
+        )
+        self.assertEqual(lines, ["n1000", "1,000"])
+
+    def test_source_output_stays_plain(self):
+        builtin.number_display = "thousands"
+        self.assertEqual(value_to_source(GulfOfMexicoNumber(1234567)), "1234567")
+
+
+class TestBooleanCoercion(unittest.TestCase):
+    VALUES = {"true": True, "false": False, "maybe": None}
+
+    def test_round_trips(self):
+        for name, value in self.VALUES.items():
+            with self.subTest(name=name):
+                boolean = GulfOfMexicoBoolean(value)
+                number = db_to_number(boolean)
+                string = db_to_string(boolean)
+                self.assertEqual(string.value, name)
+                self.assertEqual(db_to_boolean(number).value, value)
+                self.assertEqual(db_to_boolean(string).value, value)
+                self.assertEqual(db_to_number(string).value, number.value)
+
+    def test_number_values(self):
+        self.assertEqual(db_to_number(GulfOfMexicoBoolean(True)).value, 1)
+        self.assertEqual(db_to_number(GulfOfMexicoBoolean(False)).value, 0)
+        self.assertEqual(db_to_number(GulfOfMexicoBoolean(None)).value, 0.5)
+
+    def test_other_strings_keep_truthiness_rules(self):
+        self.assertTrue(db_to_boolean(GulfOfMexicoString("hello")).value)
+        self.assertFalse(db_to_boolean(GulfOfMexicoString("")).value)
+        self.assertIsNone(db_to_boolean(GulfOfMexicoString("   ")).value)
+
+
+class TestValueApi(unittest.TestCase):
+    def test_constructors(self):
+        self.assertEqual(GulfOfMexicoValue.number(2.5), GulfOfMexicoNumber(2.5))
+        self.assertEqual(GulfOfMexicoValue.string("hi"), GulfOfMexicoString("hi"))
+        values = GulfOfMexicoValue.list(
+            [GulfOfMexicoValue.number(1), GulfOfMexicoValue.string("a")]
+        )
+        self.assertIsInstance(values, GulfOfMexicoList)
+        self.assertEqual(db_to_string(values).value, "[1, a]")
+
+    def test_accessors_read_values_back(self):
+        self.assertEqual(GulfOfMexicoValue.number(3).as_number(), 3)
+        self.assertEqual(GulfOfMexicoValue.string("hi").as_string(), "hi")
+        self.assertIs(GulfOfMexicoBoolean(True).as_bool(), True)
+        self.assertIs(GulfOfMexicoBoolean(False).as_bool(), False)
+
+    def test_accessors_return_none_for_other_types(self):
+        number, string = GulfOfMexicoValue.number(1), GulfOfMexicoValue.string("1")
+        self.assertIsNone(number.as_string())
+        self.assertIsNone(number.as_bool())
+        self.assertIsNone(string.as_number())
+        self.assertIsNone(GulfOfMexicoBoolean(None).as_bool())
+
+
+if __name__ == "__main__":
+    unittest.main()
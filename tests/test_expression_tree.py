@@ -0,0 +1,115 @@
+"""Tests for expression parsing in gulfofmexico/processor/expression_tree.py."""
+
+import sys
+import unittest
+
+from gulfofmexico.base import InterpretationError, OperatorType, TokenType
+from gulfofmexico.processor.expression_tree import (
+    ExpressionNode,
+    ValueNode,
+    build_expression_tree,
+)
+from gulfofmexico.processor.lexer import tokenize
+from helpers import run_code
+
+
+def parse(source: str):
+    tokens = [t for t in tokenize("__test__", source) if t.type != TokenType.NEWLINE]
+    while tokens and tokens[-1].type == TokenType.WHITESPACE:
+        tokens.pop()
+    return build_expression_tree("__test__", tokens, source)
+
+
+class TestConfidenceMarkers(unittest.TestCase):
+    def test_bang_inside_parentheses_is_not_a_terminator(self):
+        tokens = tokenize("__test__", "print((5!) + 3)!\n")
+        types = [t.type for t in tokens]
+        self.assertIn(TokenType.CONFIDENCE, types)
+        self.assertEqual(types.count(TokenType.BANG), 1)
+
+    def test_confidence_recorded_on_sub_value(self):
+        tree = parse("(5!!) + 3")
+        self.assertIsInstance(tree, ExpressionNode)
+        self.assertIsInstance(tree.left, ValueNode)
+        self.assertEqual(tree.left.confidence, 2)
+        self.assertEqual(tree.right.confidence, 0)
+
+    def test_confidence_marked_expression_evaluates(self):
+        self.assertEqual(run_code("print((5!) + 3)!\n"), "8\n")
+        self.assertEqual(run_code("const x = (2!!!) * 4!\nprint(x)!\n"), "8\n")
+
+
+class TestOperatorLikeStrings(unittest.TestCase):
+    def test_string_with_operator_text_is_a_value(self):
+        tree = parse('"+"')
+        self.assertIsInstance(tree, ValueNode)
+        self.assertEqual(tree.name_or_value.value, "+")
+
+    def test_operator_strings_as_operands(self):
+        self.assertEqual(run_code('const s = "a"+"-"!\nprint(s)!\n'), "a-\n")
+
+
+class TestAdjacentStrings(unittest.TestCase):
+    def test_two_literals_merge(self):
+        tree = parse('"Hello, " "world"')
+        self.assertIsInstance(tree, ValueNode)
+        self.assertEqual(tree.name_or_value.value, "Hello, world")
+        self.assertEqual(run_code('print("Hello, " "world")!\n'), "Hello, world\n")
+
+    def test_three_literals_merge(self):
+        self.assertEqual(parse('"a" "b" \'c\'').name_or_value.value, "abc")
+        self.assertEqual(run_code('const s = "a" "b" "c"!\nprint(s)!\n'), "abc\n")
+
+    def test_interpolated_string_is_added_at_runtime(self):
+        tree = parse('"a" "b${n}" "c"')
+        self.assertIsInstance(tree, ExpressionNode)
+        self.assertEqual(tree.operator, OperatorType.ADD)
+        output = run_code('const n = 5!\nconst s = "a" "n=${n}" "c"!\nprint(s)!\n')
+        self.assertEqual(output, "an=5c\n")
+
+    def test_merging_does_not_create_interpolation(self):
+        self.assertEqual(run_code('const s = "$" "{n}"!\nprint(s)!\n'), "${n}\n")
+
+    def test_escapes_do_not_run_across_the_join(self):
+        self.assertIsInstance(parse('"a\\" "n"'), ExpressionNode)
+        self.assertEqual(run_code('print("a\\" "n")!\n'), "a\\n\n")
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('print("\\u" "{41}")!\n')
+        self.assertIn("Invalid unicode escape", str(ctx.exception))
+        self.assertEqual(run_code('print("x\\n" "y")!\n'), "x\ny\n")
+
+
+class TestOperatorPrecedence(unittest.TestCase):
+    def test_equal_whitespace_applies_left_to_right(self):
+        tree = parse("a+b*c")
+        self.assertEqual(tree.operator, OperatorType.MUL)
+        self.assertEqual(run_code("const r = 1+2*3!\nprint(r)!\n"), "9\n")
+        self.assertEqual(run_code("const r = (15 + 3) * 2!\nprint(r)!\n"), "36\n")
+
+    def test_whitespace_groups_first(self):
+        tree = parse("a+b * c")
+        self.assertEqual(tree.operator, OperatorType.MUL)
+        self.assertEqual(run_code("const r = 1 + 2*3!\nprint(r)!\n"), "7\n")
+
+
+class TestLongChains(unittest.TestCase):
+    def test_ten_thousand_term_addition(self):
+        code = "const r = " + "+".join(["1"] * 10_000) + "!\nprint(r)!\n"
+        limit = sys.getrecursionlimit()
+        # neither parsing nor evaluating may recurse once per term
+        sys.setrecursionlimit(1000)
+        try:
+            output = run_code(code)
+        finally:
+            sys.setrecursionlimit(limit)
+        self.assertEqual(output, "10000\n")
+
+    def test_chain_parses_left_to_right(self):
+        tree = parse("a-b-c")
+        self.assertIsInstance(tree.left, ExpressionNode)
+        self.assertEqual(tree.right.name_or_value.value, "c")
+        self.assertEqual(run_code("const r = 10-2-3!\nprint(r)!\n"), "5\n")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,1336 @@
+"""Tests for statement execution in the production interpreter."""
+
+import io
+import sys
+import tempfile
+import time
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+from enum import Enum
+from types import SimpleNamespace
+from unittest.mock import patch
+
+import gulfofmexico.base as base
+import gulfofmexico.interpreter as interpreter
+from gulfofmexico import run_sections
+from gulfofmexico.base import (
+    InterpretationError,
+    OperatorType,
+    ParseError,
+    TypeMismatchError,
+    UndefinedNameError,
+)
+from gulfofmexico.interpreter import InputEvent
+from gulfofmexico.builtin import (
+    KEYWORDS,
+    GulfOfMexicoList,
+    GulfOfMexicoNumber,
+    Name,
+)
+from helpers import run_code, value_of
+
+
+class TestStatementBudget(unittest.TestCase):
+    def tearDown(self):
+        interpreter.max_statements = None
+
+    def test_budget_exceeded_by_long_program(self):
+        interpreter.max_statements = 10
+        program = "".join(f"var x{i} = {i}!\n" for i in range(50))
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(program)
+        self.assertIn("Statement budget exceeded", str(ctx.exception))
+
+    def test_budget_stops_unbounded_recursion(self):
+        interpreter.max_statements = 100
+        with self.assertRaises(InterpretationError):
+            run_code("function spin() => spin()!\nspin()!\n")
+
+    def test_program_within_budget_runs(self):
+        interpreter.max_statements = 10
+        self.assertEqual(run_code("const x = 1!\nprint(x)!\n"), "1\n")
+
+
+class TestStopWhen(unittest.TestCase):
+    def test_stopped_when_no_longer_fires(self):
+        output = run_code(
+            "var x = 0!\n"
+            "when x > 5 {\n"
+            '   print("fired")!\n'
+            "}\n"
+            "const handle = last_when()!\n"
+            "x = 7!\n"
+            "print(stop_when(handle))!\n"
+            "x = 8!\n"
+            "print(stop_when(handle))!\n"
+        )
+        self.assertEqual(output, "fired\ntrue\nfalse\n")
+
+    def test_stopping_one_when_keeps_the_others(self):
+        output = run_code(
+            "var x = 0!\n"
+            "when x > 5 {\n"
+            '   print("first")!\n'
+            "}\n"
+            "const first = last_when()!\n"
+            "when x > 5 {\n"
+            '   print("second")!\n'
+            "}\n"
+            "stop_when(first)!\n"
+            "x = 7!\n"
+        )
+        self.assertEqual(output, "second\n")
+
+
+class TestPersistedGlobals(unittest.TestCase):
+    def setUp(self):
+        self.home = tempfile.TemporaryDirectory()
+        self.env = patch.dict("os.environ", {"HOME": self.home.name})
+        self.env.start()
+        interpreter.save_local_immutable_constant("stored", None, 0)
+
+    def tearDown(self):
+        interpreter.load_persisted_globals = True
+        self.env.stop()
+        self.home.cleanup()
+
+    def load(self):
+        namespaces = [KEYWORDS.copy()]
+        interpreter.load_global_gulfofmexico_variables(namespaces)
+        interpreter.load_local_immutable_constants(namespaces)
+        return namespaces[-1]
+
+    def test_loads_stored_globals_by_default(self):
+        self.assertIn("stored", self.load())
+
+    def test_skips_stored_globals_when_disabled(self):
+        interpreter.load_persisted_globals = False
+        self.assertNotIn("stored", self.load())
+
+
+class TestRunSections(unittest.TestCase):
+    def setUp(self):
+        interpreter.load_persisted_globals = False
+
+    def tearDown(self):
+        interpreter.load_persisted_globals = True
+
+    def test_captures_phase_timings(self):
+        files = [(None, "const const x = 1!\nprint(x)!\n"), ("lib", "print(2)!\n")]
+        stdout = io.StringIO()
+        with redirect_stdout(stdout), redirect_stderr(io.StringIO()):
+            timings = run_sections(files)
+        self.assertEqual(stdout.getvalue().splitlines(), ["1", "2"])
+        for phase in (timings.tokenize, timings.parse, timings.execute):
+            self.assertGreaterEqual(phase, 0.0)
+        self.assertIn("execute:", timings.summary())
+
+    def run_both_ways(self, files) -> tuple[str, str]:
+        outputs = []
+        for stream in (False, True):
+            stdout = io.StringIO()
+            with redirect_stdout(stdout), redirect_stderr(io.StringIO()):
+                run_sections(files, stream=stream)
+            outputs.append(stdout.getvalue())
+        return outputs[0], outputs[1]
+
+    def test_streaming_matches_batch(self):
+        files = [
+            (
+                None,
+                "var var x = 0!\n"
+                "when x > 1 {\n"
+                '   print("big")!\n'
+                "}\n"
+                "function double(n) => n*2!\n"
+                "x = double(3)!\n"
+                "export x to \"lib\"!\n",
+            ),
+            ("lib", "import x!\nprint(x)!\n"),
+        ]
+        batch, streamed = self.run_both_ways(files)
+        self.assertEqual(streamed, batch)
+        self.assertEqual(streamed.splitlines(), ["big", "6"])
+
+    def test_streaming_runs_statements_before_later_ones_parse(self):
+        files = [(None, 'print(1)!\n"x" {\n   print(2)!\n}\n')]
+        stdout = io.StringIO()
+        with redirect_stdout(stdout), redirect_stderr(io.StringIO()):
+            with self.assertRaises(InterpretationError):
+                run_sections(files, stream=True)
+        self.assertEqual(stdout.getvalue(), "1\n")
+
+
+class TestNameResolution(unittest.TestCase):
+    def test_innermost_scope_wins(self):
+        output = run_code(
+            "const const x = 1!\n"
+            "function g() => x!\n"
+            "function f() => {\n"
+            "   const const x = 2!\n"
+            "   return g()!\n"
+            "}\n"
+            "print(g())!\n"
+            "print(f())!\n"
+            "print(x)!\n"
+        )
+        # a function sees its callers' names, so f's x shadows the global one
+        self.assertEqual(output.splitlines(), ["1", "2", "1"])
+
+    def test_many_names_under_a_deep_call_stack(self):
+        names = 2000
+        depth = 200
+        lines = [f"const const n{i} = {i}!" for i in range(names)]
+        for i in range(depth):
+            inner = f"f{i + 1}(x)" if i + 1 < depth else f"x+n{names - 1}"
+            lines.append(f"function f{i}(x) => {inner}!")
+        lines.append("print(f0(n7))!")
+        output = run_code("\n".join(lines) + "\n")
+        self.assertEqual(output, f"{7 + names - 1}\n")
+
+    def test_shadowing_after_a_cached_lookup(self):
+        depth = 2 * interpreter.CACHED_LOOKUP_DEPTH
+        lines = ["const const x = 1!"]
+        lines += [f"function f{i}() => f{i + 1}()!" for i in range(depth)]
+        lines += [
+            f"function f{depth}() => {{",
+            "   print(x)!",
+            "   const const x = 2!",
+            "   print(x)!",
+            "   delete x!",
+            "   print(x)!",
+            "}",
+            "f0()!",
+        ]
+        output = run_code("\n".join(lines) + "\n")
+        self.assertEqual(output.splitlines(), ["1", "2", "1"])
+
+    def test_cached_lookups_match_a_scan(self):
+        def scan(name, namespaces):
+            for namespace in reversed(namespaces):
+                if name in namespace:
+                    return namespace[name]
+            return None
+
+        names = ["a", "b", "c", "missing"]
+        # every namespace binds two of a, b and c
+        namespaces = [
+            {
+                name: Name(name, GulfOfMexicoNumber(i))
+                for name in names[:3]
+                if name != names[i % 3]
+            }
+            for i in range(3 * interpreter.CACHED_LOOKUP_DEPTH)
+        ]
+        for step in range(len(namespaces)):
+            for name in names:
+                with self.subTest(step=step, name=name):
+                    self.assertIs(
+                        interpreter.get_name_from_namespaces(name, namespaces),
+                        scan(name, namespaces),
+                    )
+            # rebind a name near the top, the way a declaration would
+            name = names[step % 3]
+            interpreter.forget_name_lookups(name)
+            namespaces[-1 - step % 5][name] = Name(name, GulfOfMexicoNumber(-step))
+
+
+class TestOperatorDispatch(unittest.TestCase):
+    def test_number_arithmetic(self):
+        self.assertEqual(value_of("7-2"), "5")
+        self.assertEqual(value_of("2^3"), "8")
+        self.assertEqual(value_of("1/0"), "undefined")
+
+    def test_string_concatenation_either_side(self):
+        self.assertEqual(value_of('"a"+1'), "a1")
+        self.assertEqual(value_of('1+"a"'), "1a")
+
+    def test_list_concatenation(self):
+        setup = "const a = [1, 2]!\nconst b = [3]!\n"
+        self.assertEqual(value_of("a+b", setup), "[1, 2, 3]")
+
+    def test_comparisons(self):
+        self.assertEqual(value_of("1<2"), "true")
+        self.assertEqual(value_of("2<=2"), "true")
+        self.assertEqual(value_of("1>2"), "false")
+        self.assertEqual(value_of("1>=2"), "false")
+
+    def test_membership(self):
+        setup = 'const m = Map()!\nm["a"] = 1!\nconst xs = [1, "b", 3]!\n'
+        cases = [
+            ('"a" in m', "true"),
+            ('"z" in m', "false"),
+            ("3 in xs", "true"),
+            ("4 in xs", "false"),
+            ('"b" in xs', "true"),
+            ('"ell" in "hello"', "true"),
+            ('"x" in "hello"', "false"),
+        ]
+        for expression, expected in cases:
+            with self.subTest(expression=expression):
+                self.assertEqual(value_of(expression, setup), expected)
+
+    def test_membership_needs_a_container(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            value_of("1 in 5")
+        self.assertIn("Cannot check membership", str(ctx.exception))
+
+    def test_most_specific_registration_wins(self):
+        lists = interpreter.lookup_binary_operation(
+            GulfOfMexicoList([]), OperatorType.ADD, GulfOfMexicoList([])
+        )
+        numbers = interpreter.lookup_binary_operation(
+            GulfOfMexicoNumber(1), OperatorType.ADD, GulfOfMexicoNumber(1)
+        )
+        self.assertIs(lists, interpreter.concatenate_lists)
+        self.assertIs(numbers, interpreter.perform_arithmetic)
+
+
+class TestOperatorMethods(unittest.TestCase):
+    VECTOR = (
+        "class Vector {\n"
+        "   var x = 0!\n"
+        "   var y = 0!\n"
+        "   function __add__(other) => {\n"
+        "      const result = new(Vector)!\n"
+        "      result.x = x + other.x!\n"
+        "      result.y = y + other.y!\n"
+        "      return result!\n"
+        "   }\n"
+        "   function __mul__(k) => {\n"
+        "      const result = new(Vector)!\n"
+        "      result.x = x * k!\n"
+        "      result.y = y * k!\n"
+        "      return result!\n"
+        "   }\n"
+        "   function __eq__(other) => x == other.x!\n"
+        "}\n"
+        "const a = new(Vector)!\na.x = 1!\na.y = 2!\n"
+        "const b = new(Vector)!\nb.x = 10!\nb.y = 20!\n"
+    )
+
+    def test_add_two_instances(self):
+        output = run_code(self.VECTOR + "const c = a + b!\nprint(c.x)!\nprint(c.y)!\n")
+        self.assertEqual(output.splitlines(), ["11", "22"])
+
+    def test_operands_are_not_changed(self):
+        output = run_code(self.VECTOR + "const c = a + b!\nprint(a.x)!\nprint(b.x)!\n")
+        self.assertEqual(output.splitlines(), ["1", "10"])
+
+    def test_right_operand_can_be_any_value(self):
+        output = run_code(self.VECTOR + "const c = a * 3!\nprint(c.y)!\n")
+        self.assertEqual(output, "6\n")
+
+    def test_equality_method(self):
+        output = run_code(
+            self.VECTOR + "b.x = 1!\nconst same = a == b!\nprint(same)!\n"
+        )
+        self.assertEqual(output, "true\n")
+
+    def test_without_a_method_the_operator_is_unchanged(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(self.VECTOR + "const d = a - b!\n")
+        self.assertIn("Cannot turn type GulfOfMexicoObject", ctx.exception.message)
+
+
+class TestCollectionMethods(unittest.TestCase):
+    SQUARES = (
+        "class Squares {\n"
+        "   var size = 4!\n"
+        "   function __len__() => size!\n"
+        "   function __index__(i) => i*i!\n"
+        "}\n"
+        "const s = new(Squares)!\n"
+    )
+
+    def test_len_calls_len_method(self):
+        output = run_code(self.SQUARES + "const n = len(s)!\nprint(n)!\n")
+        self.assertEqual(output, "4\n")
+
+    def test_indexing_calls_index_method(self):
+        output = run_code(
+            self.SQUARES + "const a = s[3]!\nconst b = s[-1]!\nprint(a)!\nprint(b)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["9", "1"])
+
+    def test_plain_objects_are_still_rejected(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(
+                "class Empty {\n   var x = 1!\n}\nconst e = new(Empty)!\nlen(e)!\n"
+            )
+        self.assertIn("Cannot take the length", ctx.exception.message)
+
+    def test_len_method_sees_the_callers_whens(self):
+        output = run_code(
+            "var var calls = 0!\n"
+            'when (calls == 1) {\n   print("called")!\n}\n'
+            "class Sized {\n"
+            "   function __len__() => {\n"
+            "      calls = calls + 1!\n"
+            "      return 2!\n"
+            "   }\n"
+            "}\n"
+            "const s = new(Sized)!\nconst n = len(s)!\nprint(n)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["called", "2"])
+
+    def test_len_method_errors_point_at_the_call(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(
+                "class Broken {\n   function __len__(x) => x!\n}\n"
+                "const b = new(Broken)!\nconst n =   len(b)!\n"
+            )
+        self.assertIn("line 5", str(ctx.exception))
+        # the marker ends where len does, not at the start of the line
+        self.assertIn("\n          ^^^^^^^\n", str(ctx.exception))
+
+
+class TestMapEquality(unittest.TestCase):
+    MAPS = (
+        'var a = Map()!\na["x"] = 1!\na["y"] = 2!\n'
+        'var b = Map()!\nb["y"] = 2!\nb["x"] = 1!\n'
+    )
+
+    def test_key_order_is_ignored(self):
+        output = run_code(self.MAPS + "const const same = a == b!\nprint(same)!\n")
+        self.assertEqual(output, "true\n")
+
+    def test_nested_maps_compare_by_value(self):
+        output = run_code(
+            self.MAPS + 'var outer_a = Map()!\nouter_a["k"] = a!\n'
+            'var outer_b = Map()!\nouter_b["k"] = b!\n'
+            "const const before = outer_a == outer_b!\nprint(before)!\n"
+            'b["x"] = 5!\n'
+            "const const after = outer_a == outer_b!\nprint(after)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["true", "false"])
+
+    def test_triple_equals_checks_identity(self):
+        output = run_code(
+            self.MAPS + "const const c = a!\n"
+            "const const distinct = a === b!\nconst const same = a === c!\n"
+            "print(distinct)!\nprint(same)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["false", "true"])
+
+
+class TestListIndexAssignment(unittest.TestCase):
+    def assign(self, index: str) -> list[str]:
+        code = f"var var a = [1, 2, 3]!\na[{index}] = 10!\nprint(a)!\n"
+        return run_code(code).splitlines()
+
+    def test_minus_one_is_first(self):
+        self.assertEqual(self.assign("-1"), ["[10, 2, 3]"])
+
+    def test_zero_is_second(self):
+        self.assertEqual(self.assign("0"), ["[1, 10, 3]"])
+
+    def test_reads_match_writes(self):
+        output = run_code(
+            "var var a = [1, 2, 3]!\na[-0.5] = 10!\nprint(a[-1])!\n"
+            "print(a[-0.5])!\nprint(a[0])!\na[0] = 20!\nprint(a)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["1", "10", "2", "[1, 10, 20, 3]"])
+
+    def test_out_of_range_negative_is_an_error(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            self.assign("-2")
+        self.assertIn("out of list bounds", str(ctx.exception))
+        self.assertIn("line 2", str(ctx.exception))
+
+    def test_nested_list(self):
+        output = run_code(
+            "const var m = [[1, 2], [3, 4]]!\nm[0][-1] = 5!\nprint(m)!\n"
+        )
+        self.assertEqual(output, "[[1, 2], [5, 4]]\n")
+
+    def test_list_inside_map(self):
+        output = run_code(
+            'const var d = new Map()!\nd["k"] = [1, 2]!\n'
+            'd["k"][0] = 9!\nprint(d["k"])!\n'
+        )
+        self.assertEqual(output, "[1, 9]\n")
+
+    def test_map_inside_list(self):
+        output = run_code(
+            'const var l = [new Map()]!\nl[-1]["a"] = 3!\nprint(l[-1]["a"])!\n'
+        )
+        self.assertEqual(output, "3\n")
+
+
+class TestCompoundAssignment(unittest.TestCase):
+    def assign(self, statement: str) -> str:
+        return run_code(f"var var x = 10!\n{statement}\nprint(x)!\n")
+
+    def test_each_operator(self):
+        cases = [
+            ("x += 5!", "15"),
+            ("x -= 3!", "7"),
+            ("x *= 2!", "20"),
+            ("x /= 4!", "2.5"),
+        ]
+        for statement, expected in cases:
+            with self.subTest(statement=statement):
+                self.assertEqual(self.assign(statement), f"{expected}\n")
+
+    def test_right_side_is_grouped(self):
+        self.assertEqual(self.assign("x *= 1 + 2!"), "30\n")
+
+    def test_list_element(self):
+        output = run_code("const var l = [1, 2, 3]!\nl[0] += 10!\nprint(l)!\n")
+        self.assertEqual(output, "[1, 12, 3]\n")
+
+    def test_constant_cannot_be_updated(self):
+        with self.assertRaises(InterpretationError):
+            run_code("const const x = 1!\nx += 1!\n")
+
+
+class TestZeroBasedIndexing(unittest.TestCase):
+    PROGRAM = (
+        'var var a = [1, 2, 3]!\nconst const s = "abc"!\n'
+        "print(a[0])!\nprint(a[-1])!\nprint(s[0])!\n"
+        "a[1] = 20!\nprint(a)!\n"
+    )
+
+    def tearDown(self):
+        interpreter.zero_based_indexing = False
+
+    def test_default_starts_at_minus_one(self):
+        self.assertEqual(
+            run_code(self.PROGRAM).splitlines(),
+            ["2", "1", "b", "[1, 2, 20]"],
+        )
+
+    def test_zero_based_mode(self):
+        interpreter.zero_based_indexing = True
+        self.assertEqual(
+            run_code(self.PROGRAM).splitlines(),
+            ["1", "3", "a", "[1, 20, 3]"],
+        )
+
+    def test_zero_based_assignment_past_the_end_appends(self):
+        interpreter.zero_based_indexing = True
+        output = run_code("var var a = [1, 2]!\na[2] = 3!\nprint(a)!\n")
+        self.assertEqual(output, "[1, 2, 3]\n")
+
+    def test_zero_based_out_of_range(self):
+        interpreter.zero_based_indexing = True
+        with self.assertRaises(InterpretationError):
+            run_code("const const a = [1, 2]!\nprint(a[-3])!\n")
+
+
+class TestConditionalBranches(unittest.TestCase):
+    def branch_taken(self, condition: str) -> str:
+        return run_code(
+            f"const const c = {condition}!\n"
+            "if c {\n"
+            '   print("true")!\n'
+            "} maybe {\n"
+            '   print("maybe")!\n'
+            "} else {\n"
+            '   print("false")!\n'
+            "}\n"
+        )
+
+    def test_true_branch(self):
+        self.assertEqual(self.branch_taken("true"), "true\n")
+
+    def test_false_branch(self):
+        self.assertEqual(self.branch_taken("false"), "false\n")
+
+    def test_maybe_branch(self):
+        self.assertEqual(self.branch_taken("maybe"), "maybe\n")
+
+    def test_else_without_maybe(self):
+        output = run_code(
+            'if 1 > 2 {\n   print("big")!\n} else {\n   print("small")!\n}\n'
+        )
+        self.assertEqual(output, "small\n")
+
+    def test_maybe_without_branch_picks_one_side(self):
+        output = run_code(
+            'if maybe {\n   print("yes")!\n} else {\n   print("no")!\n}\n'
+        )
+        self.assertIn(output, ["yes\n", "no\n"])
+
+
+class TestMatchStatement(unittest.TestCase):
+    def arm_taken(self, value: str) -> str:
+        return run_code(
+            f"const const x = {value}!\n"
+            "match x {\n"
+            '   1 => { print("one")! },\n'
+            '   "two" => { print("two")! },\n'
+            '   _ => { print("other")! }\n'
+            "}\n"
+        )
+
+    def test_first_equal_pattern_runs(self):
+        self.assertEqual(self.arm_taken("1"), "one\n")
+        self.assertEqual(self.arm_taken('"two"'), "two\n")
+
+    def test_default_arm(self):
+        self.assertEqual(self.arm_taken("3"), "other\n")
+
+    def test_no_match_without_default(self):
+        output = run_code('match 5 {\n   1 => { print("one")! }\n}\nprint("done")!\n')
+        self.assertEqual(output, "done\n")
+
+    def test_default_must_come_last(self):
+        with self.assertRaises(ParseError) as ctx:
+            run_code("match 1 {\n   _ => { print(1)! }\n   1 => { print(2)! }\n}\n")
+        self.assertIn("must come last", ctx.exception.message)
+
+    def test_aliased_match(self):
+        output = run_code(
+            "const const mm = match!\n"
+            'mm 2 {\n   1 => { print("one")! },\n   2 => { print("two")! }\n}\n'
+        )
+        self.assertEqual(output, "two\n")
+
+    def test_patterns_use_the_eq_method(self):
+        output = run_code(
+            "class Point {\n"
+            "   var x = 0!\n"
+            "   function __eq__(other) => x == other!\n"
+            "}\n"
+            "const p = new(Point)!\np.x = 3!\n"
+            'match p {\n   1 => { print("one")! },\n   3 => { print("three")! }\n}\n'
+        )
+        self.assertEqual(output, "three\n")
+
+
+class TestRepeatStatement(unittest.TestCase):
+    def tearDown(self):
+        interpreter.max_statements = None
+
+    def test_counted_repeat(self):
+        output = run_code(
+            "var var n = 0!\n"
+            "repeat 3 {\n"
+            "   n = n+1!\n"
+            "   print(n)!\n"
+            "}\n"
+        )
+        self.assertEqual(output, "1\n2\n3\n")
+
+    def test_fractional_count_is_floored(self):
+        self.assertEqual(run_code('repeat 2.9 {\n   print("hi")!\n}\n'), "hi\nhi\n")
+
+    def test_zero_and_negative_counts_run_zero_times(self):
+        for count in ["0", "0-2"]:
+            with self.subTest(count=count):
+                output = run_code(
+                    f'repeat {count} {{\n   print("hi")!\n}}\nprint("done")!\n'
+                )
+                self.assertEqual(output, "done\n")
+
+    def test_empty_body_is_capped_by_budget(self):
+        interpreter.max_statements = 10
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("repeat 1000000 {\n}\n")
+        self.assertIn("Statement budget exceeded", str(ctx.exception))
+
+
+class TestStringEscapes(unittest.TestCase):
+    def test_basic_escapes(self):
+        output = run_code('print("a\\tb\\\\n")!\n')
+        self.assertEqual(output, "a\tb\\n\n")
+
+    def test_bmp_code_point(self):
+        self.assertEqual(run_code('print("caf\\u{e9}")!\n'), "caf\u00e9\n")
+
+    def test_astral_code_point(self):
+        output = run_code(
+            'const const crab = "\\u{1F980}"!\n'
+            "const const n = char_code(crab)!\nprint(n)!\nprint(len(crab))!\n"
+        )
+        self.assertEqual(output.splitlines(), ["129408", "1"])
+
+    def test_invalid_code_points(self):
+        for escape in ["\\u{110000}", "\\u{zz}", "\\u{}", "\\u{D800}"]:
+            with self.subTest(escape=escape):
+                with self.assertRaises(InterpretationError) as ctx:
+                    run_code(f'print("{escape}")!\n')
+                self.assertIn("Invalid unicode escape", str(ctx.exception))
+
+    def test_four_digit_code_point(self):
+        self.assertEqual(run_code('print("caf\\u00e9")!\n'), "caf\u00e9\n")
+
+    def test_unknown_escape_is_an_error(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('print("a\\qb")!\n')
+        self.assertIn("Invalid escape sequence: \\q", str(ctx.exception))
+
+    def test_interpolated_string_decodes_escapes(self):
+        output = run_code('const const x = 1!\nprint("a\\t${x}\\n")!\n')
+        self.assertEqual(output, "a\t1\n\n")
+
+    def test_interpolated_values_are_not_decoded(self):
+        output = run_code('const const s = "back\\\\slash"!\nprint("${s}\\${s}")!\n')
+        self.assertEqual(output, "back\\slash${s}\n")
+
+
+class TestShadowingLint(unittest.TestCase):
+    def tearDown(self):
+        interpreter.warn_on_shadowing = False
+
+    def warnings(self, code: str) -> list[str]:
+        stderr = io.StringIO()
+        run_code(code, stderr=stderr)
+        return [line for line in stderr.getvalue().splitlines() if "warning" in line]
+
+    def test_shadowing_print_warns_under_lint(self):
+        interpreter.warn_on_shadowing = True
+        self.assertEqual(
+            self.warnings("const const print = 1!\n"),
+            ["__test__, line 1: warning: print shadows a builtin"],
+        )
+
+    def test_no_warning_by_default(self):
+        self.assertEqual(self.warnings("const const print = 1!\n"), [])
+
+    def test_shadowing_an_outer_const(self):
+        interpreter.warn_on_shadowing = True
+        warnings = self.warnings(
+            "const const limit = 1!\n"
+            "function f() => {\n   const const limit = 2!\n}\n"
+            "f()!\n"
+        )
+        self.assertEqual(len(warnings), 1)
+        self.assertIn("limit shadows a const from an outer scope", warnings[0])
+
+    def test_ordinary_declarations_do_not_warn(self):
+        interpreter.warn_on_shadowing = True
+        self.assertEqual(
+            self.warnings(
+                "var var n = 1!\nfunction f() => {\n   var var n = 2!\n}\nf()!\n"
+            ),
+            [],
+        )
+
+
+class TestReservedWords(unittest.TestCase):
+    def test_aliases_to_reserved_words_are_rejected(self):
+        for word in ["else", "to", "new", "async"]:
+            with self.subTest(word=word):
+                with self.assertRaises(InterpretationError) as ctx:
+                    run_code(f"const const {word} = if!\n")
+                self.assertIn("reserved", str(ctx.exception))
+
+    def test_reserved_words_are_still_ordinary_names(self):
+        output = run_code(
+            "const const to = 1!\nvar var new = 2!\nconst const sum = to + new!\n"
+            "print(sum)!\n"
+        )
+        self.assertEqual(output, "3\n")
+
+    def test_other_aliases_still_work(self):
+        output = run_code(
+            'const const zz = if!\nzz true {\n   print("aliased")!\n}\n'
+        )
+        self.assertEqual(output, "aliased\n")
+
+
+class TestConciseFunctions(unittest.TestCase):
+    def test_arrow_body_is_returned(self):
+        output = run_code("fn inc(x) => x + 1!\nconst const r = inc(4)!\nprint(r)!\n")
+        self.assertEqual(output, "5\n")
+
+    def test_matches_block_body(self):
+        output = run_code(
+            "func add(a, b) => a+b!\n"
+            "func add_block(a, b) => {\n   return a+b!\n}\n"
+            "const const x = add(2, 3)!\nconst const y = add_block(2, 3)!\n"
+            "print(x)!\nprint(y)!\n"
+        )
+        self.assertEqual(output, "5\n5\n")
+
+    def test_block_body_closed_with_bang(self):
+        output = run_code(
+            "function add(a, b) => {\n   return a+b!\n}!\n"
+            "const const x = add(2, 3)!\nprint(x)!\n"
+        )
+        self.assertEqual(output, "5\n")
+
+
+class TestNestedFunctions(unittest.TestCase):
+    def test_returned_function_closes_over_param(self):
+        output = run_code(
+            "function make_adder(amount) => {\n"
+            "   function add(x) => x+amount!\n"
+            "   return add!\n"
+            "}\n"
+            "const const add5 = make_adder(5)!\n"
+            "const const add7 = make_adder(7)!\n"
+            "const const a = add5(2)!\nconst const b = add7(2)!\n"
+            "print(a)!\nprint(b)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["7", "9"])
+
+    def test_nested_function_is_local_to_its_call(self):
+        with self.assertRaises(InterpretationError):
+            run_code(
+                "function outer(k) => {\n"
+                "   function inner(x) => x+k!\n"
+                "   return inner(1)!\n"
+                "}\n"
+                "const const r = outer(5)!\nprint(inner(1))!\n"
+            )
+
+
+class TestReturnDebugMarker(unittest.TestCase):
+    def call(self, body: str) -> list[str]:
+        code = f"function f(x) => {{\n   {body}\n}}\nconst const r = f(3)!\nprint(r)!\n"
+        return run_code(code).splitlines()
+
+    def test_plain_return(self):
+        self.assertEqual(self.call("return x!"), ["3"])
+
+    def test_debug_return_keeps_value(self):
+        lines = self.call("return x?")
+        self.assertIn("\x1b[33mExpression evaluates to value 3.\x1b[39m", lines)
+        self.assertEqual(lines[-1], "3")
+
+    def test_bare_debug_return_points_at_keyword(self):
+        lines = self.call("return?")
+        self.assertTrue(any("__test__, line 2" in line for line in lines))
+        self.assertIn("     return?", lines)
+        self.assertIn("     ^^^^^^", lines)
+        self.assertEqual(lines[-1], "undefined")
+
+
+class TestReturnType(unittest.TestCase):
+    def test_number_return_type_coerces_string(self):
+        output = run_code(
+            'function count(): Number => {\n   return "5"!\n}\n'
+            "const const n = count()!\nprint(n+1)!\n"
+        )
+        self.assertEqual(output, "6\n")
+
+    def test_arrow_function_with_arguments(self):
+        output = run_code(
+            "function same(x): Number => x!\n"
+            'const const n = same("7")!\nprint(n+1)!\n'
+        )
+        self.assertEqual(output, "8\n")
+
+    def test_unknown_type_is_ignored(self):
+        output = run_code(
+            "function same(x): Whatever => x!\n"
+            'const const n = same("7")!\nprint(n+1)!\n'
+        )
+        self.assertEqual(output, "71\n")
+
+    def test_unconvertible_value_is_an_error(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('function same(x): Number => x!\nprint(same("abc"))!\n')
+        self.assertIn("Cannot turn abc into a Number", str(ctx.exception))
+
+
+class TestExport(unittest.TestCase):
+    def export(self, code: str) -> dict:
+        exported: list = []
+        run_code(code, exported_names=exported)
+        return {name: value for _, name, value in exported}
+
+    def test_export_several_names(self):
+        exported = self.export(
+            'const const a = 1!\nconst const b = 2!\nexport a, b to "main"!\n'
+        )
+        self.assertEqual(sorted(exported), ["a", "b"])
+        output = run_code(
+            "import a, b!\nprint(a+b)!\n", importable_names={"main": exported}
+        )
+        self.assertEqual(output.splitlines(), ["3"])
+
+    def test_export_to_own_file_is_rejected(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            self.export('const const a = 1!\nexport a to "__test__"!\n')
+        self.assertIn("file doing the exporting", str(ctx.exception))
+
+    def test_export_wildcard(self):
+        exported = self.export(
+            'const const a = 1!\nfunction f() => 5!\nexport * to "main"!\n'
+        )
+        self.assertEqual(sorted(exported), ["a", "f"])
+        output = run_code(
+            "import a, f!\nprint(a+f())!\n", importable_names={"main": exported}
+        )
+        self.assertEqual(output.splitlines(), ["6"])
+
+    def test_export_to_stdout_prints_source(self):
+        exported: list = []
+        output = run_code(
+            'const const a = [1, "x"]!\nfunction f() => 5!\nexport a, f to "-"!\n',
+            exported_names=exported,
+        )
+        self.assertEqual(
+            output.splitlines(),
+            ['const const a = [1, "x"]!', "// f: value cannot be written as source"],
+        )
+        self.assertEqual(exported, [])
+
+    def test_stdout_export_runs_back(self):
+        source = run_code('const const n = 4!\nexport n to "-"!\n')
+        self.assertEqual(run_code(source + "print(n)!\n"), "4\n")
+
+
+class TestLayeredLifetimes(unittest.TestCase):
+    def test_short_lifetime_reveals_longer_lived_value(self):
+        output = run_code(
+            'const const name = "Luke"!\n'
+            'const const name<2> = "Lu"!\n'
+            "print(name)!\n"
+            "print(name)!\n"
+            "print(name)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["Lu", "Luke", "Luke"])
+
+    def test_variable_with_nothing_left_is_removed(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("const const gone<1> = 5!\nprint(gone)!\n")
+        self.assertIn("Undefined name: gone", str(ctx.exception))
+
+    def test_higher_confidence_declaration_wins(self):
+        output = run_code("var var x = 10!!!\nvar var x = 20!\nprint(x)!\n")
+        self.assertEqual(output.splitlines(), ["10"])
+
+    def test_assignment_replaces_value_regardless_of_confidence(self):
+        output = run_code(
+            "var var x = 1!!\nx = 2!\nprint(x)!\nx = 3!!\nx = 4!\nprint(x)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["2", "4"])
+
+    def test_assignment_keeps_layered_value_beneath(self):
+        output = run_code(
+            "var var x<3> = 1!!!\nvar var x = 2!\nx = 3!\n"
+            "print(x)!\nprint(x)!\nprint(x)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["3", "3", "2"])
+
+    def test_reverse_replaces_string_value(self):
+        output = run_code('var var s = "abc"!!\nreverse s!\nprint(s)!\n')
+        self.assertEqual(output.splitlines(), ["cba"])
+
+
+class TestWhenWithUndefinedNames(unittest.TestCase):
+    def test_registers_before_dependencies_exist(self):
+        output = run_code(
+            "var var a = 1!\n"
+            "when a < b & b < c {\n"
+            '   print("fired")!\n'
+            "}\n"
+            'print("registered")!\n'
+            "var var b = 2!\n"
+            "var var c = 3!\n"
+            "c = 0!\n"
+            "c = 4!\n"
+        )
+        self.assertEqual(output.splitlines(), ["registered", "fired", "fired"])
+
+
+class TestDeferredWhen(unittest.TestCase):
+    PROGRAM = (
+        "var x = 10!\n"
+        "when x > 5 {\n"
+        '   print("fired")!\n'
+        "}\n"
+        'print("registered")!\n'
+        "x = 7!\n"
+    )
+
+    def tearDown(self):
+        interpreter.defer_when_statements = False
+
+    def test_when_fires_on_registration_by_default(self):
+        self.assertEqual(run_code(self.PROGRAM), "fired\nregistered\nfired\n")
+
+    def test_deferred_when_waits_for_a_change(self):
+        interpreter.defer_when_statements = True
+        self.assertEqual(run_code(self.PROGRAM), "registered\nfired\n")
+
+
+class TestWhenWatcherBookkeeping(unittest.TestCase):
+    def test_watchers_follow_reassigned_lists(self):
+        output = run_code(
+            "var var xs = [1, 2]!\n"
+            "const const old = xs!\n"
+            "when xs[-1] > 5 {\n"
+            "   print(xs[-1])!\n"
+            "}\n"
+            "xs = [6]!\n"
+            "old[-1] = 9!\n"
+            "xs = [8]!\n"
+        )
+        self.assertEqual(output.splitlines(), ["6", "8"])
+
+    def test_many_whens_each_fire_once_per_assignment(self):
+        whens = "".join(
+            f"when x > {i} {{\n   hits = hits + 1!\n}}\n" for i in range(500)
+        )
+        output = run_code(
+            "var var x = 0!\nvar var hits = 0!\n"
+            + whens
+            + "x = 1!\nx = 250!\nx = 500!\nprint(hits)!\n"
+        )
+        self.assertEqual(output, f"{1 + 250 + 500}\n")
+
+
+class TestAfterDelay(unittest.TestCase):
+    def setUp(self):
+        self.now = 0.0
+        interpreter.after_clock = lambda: self.now
+
+    def tearDown(self):
+        interpreter.after_clock = time.monotonic
+        interpreter.scheduled_afters.clear()
+
+    def test_duration_parsing(self):
+        self.assertEqual(interpreter.parse_after_duration("2s"), 2.0)
+        self.assertEqual(interpreter.parse_after_duration("250ms"), 0.25)
+        self.assertEqual(interpreter.parse_after_duration("1m"), 60.0)
+        self.assertIsNone(interpreter.parse_after_duration("keydown"))
+
+    def test_body_waits_for_the_delay(self):
+        output = run_code(
+            'after "1s" {\n   print("later")!\n}\nprint("scheduled")!\nprint("done")!\n'
+        )
+        self.assertEqual(output, "scheduled\ndone\n")
+        self.now = 1.0
+        stdout = io.StringIO()
+        with redirect_stdout(stdout):
+            interpreter.run_due_after_bodies()
+        self.assertEqual(stdout.getvalue(), "later\n")
+        self.assertEqual(interpreter.scheduled_afters, [])
+
+    def test_due_body_runs_before_the_next_statement(self):
+        output = run_code(
+            'after "0s" {\n   print("later")!\n}\nprint("next")!\nprint("done")!\n'
+        )
+        self.assertEqual(output, "later\nnext\ndone\n")
+
+    def test_body_errors_are_reported(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('after "0s" {\n   print(missing)!\n}\nprint("next")!\n')
+        self.assertIn("missing", str(ctx.exception))
+
+
+class TestInjectedInputEvents(unittest.TestCase):
+    def setUp(self):
+        interpreter.use_system_input = False
+
+    def tearDown(self):
+        interpreter.use_system_input = True
+        interpreter.after_event_handlers.clear()
+
+    def inject(self, *input_events: InputEvent) -> str:
+        stdout = io.StringIO()
+        with redirect_stdout(stdout):
+            interpreter.inject_events(list(input_events))
+        return stdout.getvalue()
+
+    def test_keydown_runs_body_with_event_object(self):
+        run_code('after "keydown" {\n   print(event.key)!\n}\n')
+        output = self.inject(
+            InputEvent("key", True, key="a"),
+            InputEvent("key", False, key="a"),
+            InputEvent("key", True, key="b"),
+        )
+        self.assertEqual(output, "a\nb\n")
+
+    def test_mouseclick_needs_press_then_release(self):
+        run_code('after "mouseclick" {\n   print(event.button)!\n}\n')
+        output = self.inject(
+            InputEvent("mouse", False, button="left"),
+            InputEvent("mouse", True, button="right", x=3, y=4),
+            InputEvent("key", False, key="right"),
+            InputEvent("mouse", False, button="right", x=3, y=4),
+        )
+        self.assertEqual(output, "right\n")
+
+    def test_invalid_event_errors(self):
+        with self.assertRaises(InterpretationError):
+            run_code('after "keysideways" {\n   print("nope")!\n}\n')
+
+    def test_event_from_a_variable(self):
+        run_code(
+            'const const name = "keydown"!\nafter name {\n   print(event.key)!\n}\n'
+        )
+        self.assertEqual(self.inject(InputEvent("key", True, key="a")), "a\n")
+
+    def test_event_from_an_expression(self):
+        run_code(
+            'const const device = "key"!\n'
+            'after device+"down" {\n   print(event.key)!\n}\n'
+        )
+        self.assertEqual(self.inject(InputEvent("key", True, key="a")), "a\n")
+
+    def test_invalid_event_from_a_variable_names_the_value(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('const const name = "nap"!\nafter name {\n   print(1)!\n}\n')
+        self.assertIn('the "after" statement: "nap"', str(ctx.exception))
+
+
+class TestInputListenerFailure(unittest.TestCase):
+    class BrokenListener:
+        def __init__(self, **callbacks):
+            raise OSError("no display")
+
+    def setUp(self):
+        broken = SimpleNamespace(Listener=self.BrokenListener)
+        pynput = SimpleNamespace(mouse=broken, keyboard=broken)
+        self.patches = [
+            patch.object(interpreter, "KEY_MOUSE_IMPORTED", True),
+            patch.dict(sys.modules, {"pynput": pynput}),
+            patch.object(interpreter, "input_listener_state", None),
+        ]
+        for p in self.patches:
+            p.start()
+
+    def tearDown(self):
+        for p in self.patches:
+            p.stop()
+        interpreter.after_event_handlers.clear()
+
+    def test_failure_is_reported_once_and_marks_unavailable(self):
+        program = 'after "keydown" {\n   print("key")!\n}\n'
+        stderr = io.StringIO()
+        with redirect_stderr(stderr):
+            interpreter.start_input_listeners()
+            interpreter.start_input_listeners()
+        self.assertEqual(stderr.getvalue().count("Input events are unavailable"), 1)
+        self.assertFalse(interpreter.input_listeners_available())
+        run_code(program)  # registering more after statements must not raise
+
+
+class TestSystemKeyNames(unittest.TestCase):
+    class KeyCode:
+        def __init__(self, char: str):
+            self.char = char
+
+    Key = Enum("Key", "space")
+
+    class RecordingListener:
+        def __init__(self, **callbacks):
+            self.callbacks = callbacks
+
+        def start(self):
+            pass
+
+    def setUp(self):
+        keyboard = SimpleNamespace(
+            Listener=self.RecordingListener, KeyCode=self.KeyCode
+        )
+        mouse = SimpleNamespace(Listener=self.RecordingListener)
+        pynput = SimpleNamespace(mouse=mouse, keyboard=keyboard)
+        self.patches = [
+            patch.object(interpreter, "KEY_MOUSE_IMPORTED", True),
+            patch.dict(sys.modules, {"pynput": pynput}),
+            patch.object(interpreter, "input_listener_state", None),
+            patch.object(interpreter, "after_listeners", []),
+        ]
+        for p in self.patches:
+            p.start()
+
+    def tearDown(self):
+        for p in self.patches:
+            p.stop()
+        interpreter.after_event_handlers.clear()
+
+    def test_character_and_named_keys(self):
+        run_code('after "keydown" {\n   print(event.key)!\n}\n')
+        (on_press,) = [
+            listener.callbacks["on_press"]
+            for listener in interpreter.after_listeners
+            if "on_press" in listener.callbacks
+        ]
+        stdout = io.StringIO()
+        with redirect_stdout(stdout):
+            on_press(self.KeyCode("."))
+            on_press(self.KeyCode("a"))
+            on_press(self.Key.space)
+        self.assertEqual(stdout.getvalue(), ".\na\nspace\n")
+
+
+class TestLazyInputListeners(unittest.TestCase):
+    def test_event_free_program_starts_no_listeners(self):
+        with patch.object(interpreter, "input_listener_state", None):
+            run_code('const x = 1!\nafter "0s" {\n   print(x)!\n}\nsleep(0.1)!\n')
+            self.assertIsNone(interpreter.input_listener_state)
+            self.assertFalse(interpreter.input_listeners_available())
+
+
+class TestDebugStatement(unittest.TestCase):
+    def tearDown(self):
+        interpreter.pause_on_debug = False
+
+    def test_dumps_line_and_local_names(self):
+        output = run_code('const x = 5!\nvar y = "hi"!\ndebug!\nprint(x)!\n')
+        self.assertIn("Paused at line 3", output)
+        self.assertIn("x: 5", output)
+        self.assertIn("y: hi", output)
+        self.assertNotIn("print:", output)
+        self.assertTrue(output.endswith("5\n"))
+
+    def test_pauses_when_interactive(self):
+        interpreter.pause_on_debug = True
+        with patch("builtins.input", return_value="") as fake_input:
+            run_code("debug!\n")
+        fake_input.assert_called_once()
+
+
+class TestInlineAssertions(unittest.TestCase):
+    def tearDown(self):
+        interpreter.assert_on_debug = False
+
+    def test_satisfied_assertion_continues(self):
+        interpreter.assert_on_debug = True
+        output = run_code("const const x = 5!\nx > 0?\nprint(x)!\n")
+        self.assertIn("Expression evaluates to value true.", output)
+        self.assertTrue(output.endswith("5\n"))
+
+    def test_violated_assertion_raises(self):
+        interpreter.assert_on_debug = True
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("const const x = -2!\nx > 0?\nprint(x)!\n")
+        self.assertEqual(
+            ctx.exception.message, "Assertion failed: expression is false."
+        )
+        self.assertEqual(ctx.exception.line, 2)
+
+    def test_only_prints_without_flag(self):
+        output = run_code("const const x = -2!\nx > 0?\nprint(x)!\n")
+        self.assertIn("Expression evaluates to value false.", output)
+        self.assertTrue(output.endswith("-2\n"))
+
+
+class TestRuntimeErrorContext(unittest.TestCase):
+    def test_builtin_error_shows_source_line_and_caret(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("const l = [1, 2]!\nconst x = l[5]!\n")
+        message = str(ctx.exception)
+        self.assertIn("line 2", message)
+        self.assertIn("const x = l[5]!", message)
+        self.assertIn("^", message)
+        self.assertIn("Indexing out of list bounds.", message)
+
+    def test_error_points_at_innermost_expression(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('const x = 1 + sqrt("a")!\n')
+        self.assertIn("^^^^", str(ctx.exception))
+
+    def caret_under_name(self, name: str) -> None:
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(f"if true {{\n\t print({name})!\n}}\n")
+        source, carets = str(ctx.exception).splitlines()[2:4]
+        self.assertNotIn("\t", source)
+        self.assertEqual(carets.index("^"), source.index(name))
+        self.assertEqual(carets.strip(), "^" * len(name))
+
+    def test_caret_lines_up_after_tab_indent(self):
+        self.caret_under_name("missing")
+
+    def test_tab_width_is_configurable(self):
+        base.TAB_WIDTH = 8
+        try:
+            self.caret_under_name("missing")
+        finally:
+            base.TAB_WIDTH = 4
+
+    def caret_under_first_token(self, code: str) -> None:
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(code)
+        source, carets = str(ctx.exception).splitlines()[2:4]
+        self.assertEqual(carets.index("^"), source.index("missing"))
+        self.assertEqual(carets.strip(), "^" * len("missing"))
+
+    def test_caret_lines_up_on_first_line(self):
+        self.caret_under_first_token("missing!\n")
+
+    def test_caret_lines_up_on_later_line(self):
+        self.caret_under_first_token("const const a = 1!\n\nmissing!\n")
+
+
+class TestCallStack(unittest.TestCase):
+    PROGRAM = (
+        "function inner(n) => n + missing!\n"
+        "function middle(n) => inner(n)!\n"
+        "function outer(n) => middle(n)!\n"
+        "print(outer(1))!\n"
+    )
+
+    def tearDown(self):
+        interpreter.show_call_stack = False
+
+    def test_error_records_active_calls(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(self.PROGRAM)
+        self.assertEqual(
+            ctx.exception.call_stack, [("outer", 4), ("middle", 3), ("inner", 2)]
+        )
+        self.assertNotIn("Call stack", str(ctx.exception))
+        self.assertEqual(interpreter.call_stack, [])
+
+    def test_verbose_error_lists_intermediate_functions(self):
+        interpreter.show_call_stack = True
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code(self.PROGRAM)
+        message = str(ctx.exception)
+        self.assertIn("Undefined name: missing", message)
+        self.assertIn(
+            "Call stack (most recent call last):\n"
+            "  outer (line 4)\n"
+            "  middle (line 3)\n"
+            "  inner (line 2)",
+            message,
+        )
+
+
+class TestErrorKinds(unittest.TestCase):
+    def test_undefined_name(self):
+        with self.assertRaises(UndefinedNameError) as ctx:
+            run_code("const const x = 1!\nprint(nope)!\n")
+        error = ctx.exception
+        self.assertEqual(error.name, "nope")
+        self.assertEqual(error.line, 2)
+        self.assertEqual(error.message, "Undefined name: nope")
+        self.assertIn("print(nope)!", str(error))
+
+    def test_calling_an_undefined_function(self):
+        with self.assertRaises(UndefinedNameError) as ctx:
+            run_code("missing(1)!\n")
+        self.assertEqual(ctx.exception.name, "missing")
+
+    def test_type_annotation_mismatch(self):
+        with self.assertRaises(TypeMismatchError) as ctx:
+            run_code('const const x: Int = "a"!\n')
+        error = ctx.exception
+        self.assertEqual((error.expected, error.got), ("Int", "GulfOfMexicoString"))
+        self.assertEqual(str(error), "Type error: expected Int, got GulfOfMexicoString")
+
+    def test_negating_a_boolean(self):
+        with self.assertRaises(TypeMismatchError) as ctx:
+            run_code("const const b = true!\nprint(-b)!\n")
+        self.assertEqual(ctx.exception.got, "GulfOfMexicoBoolean")
+        self.assertEqual(ctx.exception.line, 2)
+
+    def test_parse_errors(self):
+        cases = [
+            ("const const x = @5!\n", 1),  # lexer
+            ("print(1)!\n  print(2)!\n", 2),  # indentation
+            ('"x" {\n   print(1)!\n}\n', 1),  # statement
+        ]
+        for source, line in cases:
+            with self.subTest(source=source):
+                with self.assertRaises(ParseError) as ctx:
+                    run_code(source)
+                self.assertEqual(ctx.exception.line, line)
+
+    def test_kinds_are_still_interpretation_errors(self):
+        with self.assertRaises(InterpretationError):
+            run_code("print(nope)!\n")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,295 @@
+"""Tests for the tokenizer in gulfofmexico/processor/lexer.py."""
+
+import unittest
+
+import gulfofmexico.processor.lexer as lexer
+from gulfofmexico.base import InterpretationError, TokenType
+from gulfofmexico.processor.lexer import tokenize
+from helpers import run_code, value_of
+
+
+class TestNameInterning(unittest.TestCase):
+    def test_repeated_names_share_one_string(self):
+        name = "".join(["counter", "Value"])  # built at runtime, so not a literal
+        tokens = tokenize("__test__", f"var var {name} = 1!\n{name} = {name} + 1!\n")
+        names = [t.value for t in tokens if t.value == name]
+        self.assertEqual(len(names), 3)
+        self.assertTrue(all(n is names[0] for n in names))
+
+    def test_name_heavy_program(self):
+        count = 500
+        declarations = "".join(f"var var v{i} = {i}!\n" for i in range(count))
+        total = "+".join(f"v{i}" for i in range(count))
+        updates = "".join(f"v{i} = v{i} + 1!\n" for i in range(count))
+        output = run_code(
+            f"{declarations}const before = {total}!\n{updates}"
+            f"const after = {total}!\nprint(before)!\nprint(after)!\n"
+        )
+        expected = sum(range(count))
+        self.assertEqual(output.splitlines(), [str(expected), str(expected + count)])
+
+
+class TestMultiLineStrings(unittest.TestCase):
+    def test_tokens_after_multiline_string_have_correct_line(self):
+        tokens = tokenize("__test__", 'const s = "a\nb\nc"!\nprint(s)!\n')
+        string_token = next(t for t in tokens if t.type == TokenType.STRING)
+        print_token = next(t for t in tokens if t.value == "print")
+        self.assertEqual(string_token.value, "a\nb\nc")
+        self.assertEqual(string_token.line, 1)
+        self.assertEqual(print_token.line, 4)
+
+    def test_error_after_multiline_string_reports_correct_line(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code('const s = "first\nsecond"!\nprint(missing_name)!\n')
+        self.assertIn("line 3", str(ctx.exception))
+        self.assertIn("print(missing_name)!", str(ctx.exception))
+
+
+class TestLineEndings(unittest.TestCase):
+    SOURCE = 'const s = "a\nb"!\n\nprint(s)!\n'
+
+    def summary(self, code: str) -> list[tuple]:
+        return [(t.type, t.value, t.line, t.col) for t in tokenize("__test__", code)]
+
+    def test_crlf_tokenizes_like_lf(self):
+        crlf = self.SOURCE.replace("\n", "\r\n")
+        self.assertEqual(self.summary(crlf), self.summary(self.SOURCE))
+
+    def test_lone_cr_tokenizes_like_lf(self):
+        cr = self.SOURCE.replace("\n", "\r")
+        self.assertEqual(self.summary(cr), self.summary(self.SOURCE))
+
+    def test_crlf_string_has_no_carriage_return(self):
+        tokens = tokenize("__test__", self.SOURCE.replace("\n", "\r\n"))
+        string_token = next(t for t in tokens if t.type == TokenType.STRING)
+        self.assertEqual(string_token.value, "a\nb")
+        print_token = next(t for t in tokens if t.value == "print")
+        self.assertEqual(print_token.line, 4)
+
+    def test_crlf_error_shows_source_line(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("const x = 1!\r\nprint(missing_name)!\r\n")
+        self.assertIn("line 2", str(ctx.exception))
+        self.assertIn("  print(missing_name)!\n", str(ctx.exception))
+
+
+class TestBlockComments(unittest.TestCase):
+    def test_comment_around_declaration_is_skipped(self):
+        code = "/* const const x = 1!\nconst const y = 2! */\nprint(3)!\n"
+        tokens = tokenize("__test__", code)
+        self.assertNotIn("const", [t.value for t in tokens])
+        print_token = next(t for t in tokens if t.value == "print")
+        self.assertEqual(print_token.line, 3)
+        self.assertEqual(run_code(code), "3\n")
+
+    def test_inline_comment(self):
+        self.assertEqual(run_code("print(/* ignored */ 5)!\n"), "5\n")
+
+    def test_comment_at_the_start_of_a_line_keeps_its_indentation(self):
+        code = "if true {\n   /* a */ print(1)!\n}\n/* b */ print(2)!\n"
+        self.assertEqual(run_code(code), "1\n2\n")
+
+    def test_error_after_comment_reports_correct_line(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            run_code("/*\n\n*/\nprint(missing)!\n")
+        self.assertIn("line 4", str(ctx.exception))
+
+    def test_comments_do_not_nest(self):
+        tokens = tokenize("__test__", "/* /* */ x */!")
+        self.assertIn("x", [t.value for t in tokens])
+
+    def test_unterminated_comment_errors(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            tokenize("__test__", "print(1)!\n/* never closed\n")
+        self.assertIn("Unterminated block comment", str(ctx.exception))
+        self.assertIn("line 2", str(ctx.exception))
+
+
+class TestMultiQuoteStrings(unittest.TestCase):
+    def test_double_double_quotes(self):
+        self.assertEqual(run_code('const s = ""hello""!\nprint(s)!\n'), "hello\n")
+
+    def test_triple_single_quotes(self):
+        self.assertEqual(run_code("const s = '''a'''!\nprint(s)!\n"), "a\n")
+
+    def test_interpolation_inside_multi_quotes(self):
+        output = run_code('const n = "X"!\nconst s = ""hi ${n}""!\nprint(s)!\n')
+        self.assertEqual(output, "hi X\n")
+
+    def test_mismatched_quote_count_errors(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            tokenize("__test__", 'const s = ""oops"!\n')
+        self.assertIn("Starting quotes do not match", str(ctx.exception))
+
+
+class TestEmptyStrings(unittest.TestCase):
+    def test_empty_string_token_consumes_both_quotes(self):
+        tokens = tokenize("__test__", 'const const s = ""!')
+        string_tokens = [t for t in tokens if t.type == TokenType.STRING]
+        self.assertEqual([t.value for t in string_tokens], [""])
+        self.assertEqual(tokens[tokens.index(string_tokens[0]) + 1].type, TokenType.BANG)
+
+    def test_empty_string_assignment_and_len(self):
+        output = run_code('const const s = ""!\nprint(len(s))!\nprint(len(""))!\n')
+        self.assertEqual(output, "0\n0\n")
+
+    def test_empty_string_concatenation(self):
+        self.assertEqual(run_code('const s = "" + "a"!\nprint(s)!\n'), "a\n")
+
+    def test_indexing_empty_string_errors(self):
+        with self.assertRaises(Exception) as ctx:
+            run_code('const s = ""!\nprint(s[-1])!\n')
+        self.assertIn("out of string bounds", str(ctx.exception))
+
+
+class TestUnknownCharacters(unittest.TestCase):
+    def tearDown(self):
+        lexer.allow_unknown_characters = False
+
+    def test_unknown_character_errors(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            tokenize("__test__", "const const x = @5!\n")
+        self.assertIn("Unknown character '@'", str(ctx.exception))
+        self.assertIn("line 1", str(ctx.exception))
+
+    def test_lenient_mode_keeps_character_in_name(self):
+        lexer.allow_unknown_characters = True
+        tokens = tokenize("__test__", "const const x = @5!\n")
+        self.assertIn("@5", [t.value for t in tokens if t.type == TokenType.NAME])
+
+
+class TestAssignArrow(unittest.TestCase):
+    def types(self, code: str) -> list[TokenType]:
+        tokens = tokenize("__test__", code)
+        return [t.type for t in tokens if t.type != TokenType.WHITESPACE]
+
+    def test_arrow_is_one_token(self):
+        self.assertIn(TokenType.ASSIGN_ARROW, self.types("const const x <- 5!"))
+
+    def test_less_than_and_less_equal_unchanged(self):
+        self.assertEqual(
+            self.types("a < b <= c < -1!"),
+            [
+                TokenType.NAME,
+                TokenType.LESS_THAN,
+                TokenType.NAME,
+                TokenType.LESS_EQUAL,
+                TokenType.NAME,
+                TokenType.LESS_THAN,
+                TokenType.SUBTRACT,
+                TokenType.NAME,
+                TokenType.BANG,
+            ],
+        )
+
+
+class TestNumberLiterals(unittest.TestCase):
+    def test_other_bases(self):
+        output = run_code(
+            "const const x = 0xFF!\nprint(x)!\nprint(0b1010)!\nprint(0o17)!\n"
+        )
+        self.assertEqual(output.splitlines(), ["255", "10", "15"])
+
+    def test_scientific_notation(self):
+        output = run_code("print(1e3)!\nprint(1.5e3)!\nprint(2E-4)!\nprint(2e+2)!\n")
+        self.assertEqual(output.splitlines(), ["1000", "1500", "0.0002", "200"])
+
+    def test_exponent_sign_is_part_of_the_number(self):
+        tokens = tokenize("__test__", "x = 2E-4 - e-1!")
+        names = [t.value for t in tokens if t.type == TokenType.NAME]
+        self.assertEqual(names, ["x", "2E-4", "e", "1"])
+
+
+class TestDigitSeparators(unittest.TestCase):
+    def test_valid_placements(self):
+        output = run_code(
+            "const const big = 1_000_000!\nprint(big)!\n"
+            "print(3_000.5)!\nprint(0xFF_FF)!\nprint(1_0e1_0)!\n"
+        )
+        self.assertEqual(
+            output.splitlines(), ["1000000", "3000.5", "65535", "100000000000"]
+        )
+
+    def test_invalid_placements(self):
+        for number in ["1__000", "1000_", "1_.5", "3._5", "1_e3", "0xFF_"]:
+            with self.subTest(number=number):
+                with self.assertRaises(InterpretationError) as ctx:
+                    tokenize("__test__", f"const const x = {number}!\n")
+                self.assertIn(f"Misplaced _ in the number {number}", str(ctx.exception))
+
+    def test_names_with_underscores_are_untouched(self):
+        tokens = tokenize("__test__", "const const _1000 = 1st_place!\n")
+        names = [t.value for t in tokens if t.type == TokenType.NAME]
+        self.assertEqual(names, ["const", "const", "_1000", "1st_place"])
+
+
+class TestCompoundAssign(unittest.TestCase):
+    def test_operator_equals_is_one_token(self):
+        for op in ["+=", "-=", "*=", "/="]:
+            with self.subTest(op=op):
+                tokens = tokenize("__test__", f"x {op} 2!")
+                compound = [t for t in tokens if t.type == TokenType.COMPOUND_ASSIGN]
+                self.assertEqual([t.value for t in compound], [op])
+
+    def test_comparisons_unchanged(self):
+        tokens = tokenize("__test__", "a +== b!")
+        self.assertNotIn(TokenType.COMPOUND_ASSIGN, [t.type for t in tokens])
+
+
+class TestSourceSlicing(unittest.TestCase):
+    def significant(self, code: str) -> list[tuple[TokenType, str]]:
+        tokens = tokenize("__test__", code)
+        return [(t.type, t.value) for t in tokens if t.type != TokenType.WHITESPACE]
+
+    def test_multi_byte_characters(self):
+        self.assertEqual(
+            self.significant('const const s = "héllo 🌊 ${名前}"!\nprint(café)!'),
+            [
+                (TokenType.NAME, "const"),
+                (TokenType.NAME, "const"),
+                (TokenType.NAME, "s"),
+                (TokenType.EQUAL, "="),
+                (TokenType.STRING, "héllo 🌊 ${名前}"),
+                (TokenType.BANG, "!"),
+                (TokenType.NEWLINE, "\n"),
+                (TokenType.NAME, "print"),
+                (TokenType.NAME, "caf"),
+                (TokenType.NAME, "é"),
+                (TokenType.BANG, "!"),
+            ],
+        )
+
+    def test_quotes_inside_multi_quote_string(self):
+        tokens = self.significant("const s = '\"\"a\"b\"'\"!")
+        self.assertIn((TokenType.STRING, 'a"b'), tokens)
+
+    def test_large_file(self):
+        lines = 20000
+        code = "".join(
+            f'const const name{i} = "value ü{i}"!\n' for i in range(lines)
+        )
+        tokens = tokenize("__test__", code)
+        strings = [t for t in tokens if t.type == TokenType.STRING]
+        self.assertEqual(len(strings), lines)
+        self.assertEqual(strings[-1].value, f"value ü{lines - 1}")
+        self.assertEqual(strings[-1].line, lines)
+        names = {t.value for t in tokens if t.type == TokenType.NAME}
+        self.assertIn(f"name{lines - 1}", names)
+
+
+class TestMinusSign(unittest.TestCase):
+    def test_subtraction_without_spaces(self):
+        self.assertEqual(value_of("5-3"), "2")
+
+    def test_subtraction_with_spaces(self):
+        self.assertEqual(value_of("5 - 3"), "2")
+
+    def test_subtraction_after_closing_paren(self):
+        self.assertEqual(value_of("(2)-1"), "1")
+
+    def test_negative_number_after_equals(self):
+        self.assertEqual(value_of("-3"), "-3")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,189 @@
+"""Tests for result display and meta-commands in gulfofmexico/repl.py."""
+
+import io
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+from pathlib import Path
+from unittest.mock import patch
+
+import gulfofmexico.interpreter as interpreter
+from gulfofmexico.builtin import (
+    GulfOfMexicoBoolean,
+    GulfOfMexicoFunction,
+    GulfOfMexicoList,
+    GulfOfMexicoNumber,
+    GulfOfMexicoString,
+    GulfOfMexicoUndefined,
+)
+from gulfofmexico.repl import GomRepl, format_repl_value
+
+
+class ReplTestCase(unittest.TestCase):
+    def setUp(self):
+        self.saved_load_globals = interpreter.load_persisted_globals
+        interpreter.load_persisted_globals = False
+        self.tmp = tempfile.TemporaryDirectory()
+        self.dir = Path(self.tmp.name)
+        self.repl = GomRepl(color=False)
+
+    def tearDown(self):
+        interpreter.load_persisted_globals = self.saved_load_globals
+        self.tmp.cleanup()
+
+    def run_repl(self, *inputs: str) -> list[str]:
+        """Feed code blocks and meta-commands to the REPL, returning stdout."""
+        stdout = io.StringIO()
+        with redirect_stdout(stdout), redirect_stderr(io.StringIO()):
+            for block in inputs:
+                if block.startswith(":"):
+                    self.repl._dispatch_command(block)
+                else:
+                    self.repl._execute(block)
+        return stdout.getvalue().splitlines()
+
+
+class TestFormatValue(unittest.TestCase):
+    def test_plain_formatting(self):
+        cases = [
+            (GulfOfMexicoNumber(3.0), "3"),
+            (GulfOfMexicoNumber(2.5), "2.5"),
+            (GulfOfMexicoString("hi"), '"hi"'),
+            (GulfOfMexicoBoolean(True), "true"),
+            (GulfOfMexicoBoolean(None), "maybe"),
+            (GulfOfMexicoUndefined(), "undefined"),
+            (
+                GulfOfMexicoList([GulfOfMexicoNumber(1), GulfOfMexicoString("a")]),
+                '[1, "a"]',
+            ),
+            (GulfOfMexicoFunction(["n"], [], False), "<function (n)>"),
+        ]
+        for value, expected in cases:
+            with self.subTest(expected=expected):
+                self.assertEqual(format_repl_value(value, color=False), expected)
+
+    def test_colored_formatting_wraps_in_ansi_codes(self):
+        text = format_repl_value(GulfOfMexicoString("hi"))
+        self.assertTrue(text.startswith("\x1b["))
+        self.assertIn('"hi"', text)
+        self.assertTrue(text.endswith("\x1b[0m"))
+
+
+class TestResultDisplay(ReplTestCase):
+    def test_result_printed_without_debug_form(self):
+        lines = self.run_repl('"hi"!')
+        self.assertEqual(lines, ['"hi"'])
+
+
+class TestSetGlobal(ReplTestCase):
+    def test_value_visible_in_later_inputs(self):
+        self.repl.set_global("limit", GulfOfMexicoNumber(7))
+        self.assertEqual(self.run_repl("print(limit)!", "print(limit)!"), ["7", "7"])
+
+
+class TestLoad(ReplTestCase):
+    def test_definitions_visible_after_load(self):
+        lib = self.dir / "lib.gom"
+        lib.write_text(
+            'const const greeting = "hi"!\nfunction double(n) => n*2!\n',
+            encoding="utf-8",
+        )
+        lines = self.run_repl(f":load {lib}", "print(greeting)!", "print(double(4))!")
+        self.assertIn("hi", lines)
+        self.assertIn("8", lines)
+
+    def test_repeated_load_reads_file_once(self):
+        lib = self.dir / "lib.gom"
+        lib.write_text("print(42)!\n", encoding="utf-8")
+        reads = []
+        real_read_text = Path.read_text
+
+        def counting_read_text(path, *args, **kwargs):
+            reads.append(path)
+            return real_read_text(path, *args, **kwargs)
+
+        with patch.object(Path, "read_text", counting_read_text):
+            lines = self.run_repl(f":load {lib}", f":load {lib}")
+        self.assertEqual(len(reads), 1)
+        self.assertEqual(lines, ["42", "42"])
+
+    def test_changed_file_is_read_again(self):
+        lib = self.dir / "lib.gom"
+        lib.write_text("print(1)!\n", encoding="utf-8")
+        self.run_repl(f":load {lib}")
+        lib.write_text("print(2)!\n", encoding="utf-8")
+        stat = lib.stat()
+        os.utime(lib, ns=(stat.st_atime_ns, stat.st_mtime_ns + 1_000_000_000))
+        self.assertEqual(self.run_repl(f":load {lib}"), ["2"])
+
+    def test_load_missing_file(self):
+        lines = self.run_repl(f":load {self.dir / 'missing.gom'}")
+        self.assertTrue(lines[0].startswith("No such file"))
+
+
+class TestSaveDeclarations(ReplTestCase):
+    def test_file_contains_declarations(self):
+        out = self.dir / "session.gom"
+        self.run_repl(
+            'const const greeting = "hi"!',
+            "var var count = 3!",
+            "const var xs = [1, 2.5, true]!",
+            f":save {out} decls",
+        )
+        self.assertEqual(
+            out.read_text(encoding="utf-8").splitlines(),
+            [
+                'const const greeting = "hi"!',
+                "var var count = 3!",
+                "var const xs = [1, 2.5, true]!",
+            ],
+        )
+
+    def test_unwritable_values_become_comments(self):
+        out = self.dir / "session.gom"
+        self.run_repl("function double(n) => n*2!", f":save {out} decls")
+        self.assertEqual(
+            out.read_text(encoding="utf-8").splitlines(),
+            ["// double: value cannot be written as source"],
+        )
+
+    def test_saved_file_loads_back(self):
+        out = self.dir / "session.gom"
+        self.run_repl("var var count = -4!", f":save {out} decls")
+        self.repl = GomRepl(color=False)
+        lines = self.run_repl(f":load {out}", "count = count + 1!", "print(count)!")
+        self.assertIn("-3", lines)
+
+    def test_floats_load_back_exactly(self):
+        out = self.dir / "session.gom"
+        self.run_repl(
+            "const const tiny = 1e-9!",
+            "const const close = 2.0000000001!",
+            "const const huge = 1e300!",
+            f":save {out} decls",
+        )
+        self.repl = GomRepl(color=False)
+        self.run_repl(f":load {out}")
+        expected_values = {"tiny": 1e-9, "close": 2.0000000001, "huge": 1e300}
+        for name, expected in expected_values.items():
+            with self.subTest(name=name):
+                value = self.repl.namespaces[-1][name].value
+                self.assertEqual(value, GulfOfMexicoNumber(expected))
+
+    def test_infinity_becomes_a_comment(self):
+        out = self.dir / "session.gom"
+        self.run_repl("const const big = 1e300 * 1e300!", f":save {out} decls")
+        self.assertEqual(
+            out.read_text(encoding="utf-8").splitlines(),
+            ["// big: value cannot be written as source"],
+        )
+
+    def test_history_is_still_the_default(self):
+        out = self.dir / "history.gom"
+        self.run_repl("var var count = 3!", f":save {out}")
+        self.assertEqual(out.read_text(encoding="utf-8"), "var var count = 3!")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,74 @@
+"""Tests for value serialization in gulfofmexico/serialize.py."""
+
+import json
+import unittest
+
+from gulfofmexico.builtin import (
+    GulfOfMexicoBoolean,
+    GulfOfMexicoList,
+    GulfOfMexicoMap,
+    GulfOfMexicoNumber,
+    GulfOfMexicoString,
+)
+from gulfofmexico.serialize import deserialize_obj, serialize_obj
+from helpers import output_lines, run_code
+
+
+def round_trip(value):
+    return deserialize_obj(json.loads(json.dumps(serialize_obj(value))))
+
+
+def nested_map() -> GulfOfMexicoMap:
+    return GulfOfMexicoMap(
+        {
+            'quote"key;;;': GulfOfMexicoList(
+                [GulfOfMexicoString('line\n"two"'), GulfOfMexicoNumber(2.5)]
+            ),
+            3: GulfOfMexicoMap({"flag": GulfOfMexicoBoolean(None)}),
+        }
+    )
+
+
+class TestRoundTrip(unittest.TestCase):
+    def test_list(self):
+        value = GulfOfMexicoList(
+            [GulfOfMexicoString("a"), GulfOfMexicoList([GulfOfMexicoNumber(1)])]
+        )
+        self.assertEqual(round_trip(value), value)
+
+    def test_nested_map_with_special_and_number_keys(self):
+        self.assertEqual(round_trip(nested_map()), nested_map())
+
+    def test_imported_value_is_usable(self):
+        lines = output_lines(
+            'import m!\nconst inner = m[3]!\nprint(inner["flag"])!\nprint(len(m))!\n',
+            importable_names={"main": {"m": round_trip(nested_map())}},
+        )
+        self.assertEqual(lines, ["maybe", "2"])
+
+
+class TestStableOrder(unittest.TestCase):
+    PROGRAM = (
+        "class Point {\n   var z = 3!\n   var a = 1!\n   var m = 2!\n}\n"
+        "const p = new(Point)!\n"
+        'const m = Map()!\nm["z"] = 1!\nm[10] = 2!\nm["a"] = 3!\n'
+        'export p, m to "main"!\n'
+    )
+
+    def setUp(self):
+        exported: list = []
+        run_code(self.PROGRAM, exported_names=exported)
+        self.values = {name: value for _, name, value in exported}
+
+    def test_exporting_twice_is_byte_identical(self):
+        first = json.dumps(serialize_obj(self.values))
+        self.assertEqual(json.dumps(serialize_obj(self.values)), first)
+
+    def test_fields_and_keys_keep_insertion_order(self):
+        values = self.values
+        self.assertEqual(list(round_trip(values["p"]).namespace), ["z", "a", "m"])
+        self.assertEqual(list(round_trip(values["m"]).self_dict), ["z", 10, "a"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,211 @@
+"""Tests for statement parsing in gulfofmexico/processor/syntax_tree.py."""
+
+import unittest
+
+import gulfofmexico.processor.syntax_tree as syntax_tree
+from gulfofmexico import check_syntax
+from gulfofmexico.base import InterpretationError, ParseError, TokenType
+from gulfofmexico.processor.lexer import tokenize
+from gulfofmexico.processor.syntax_tree import (
+    Conditional,
+    VariableDeclaration,
+    generate_syntax_tree,
+    parse_next_statement,
+)
+from helpers import run_code
+
+TWO_ERRORS = (
+    '"x" {\n'
+    "   print(1)!\n"
+    "}\n"
+    "print(2)!\n"
+    '"y" {\n'
+    "   print(3)!\n"
+    "}\n"
+)
+
+
+def parse(code: str):
+    return generate_syntax_tree("__test__", tokenize("__test__", code), code)
+
+
+class TestParseErrorRecovery(unittest.TestCase):
+    def tearDown(self):
+        syntax_tree.collect_parse_errors = False
+
+    def test_fail_fast_by_default(self):
+        with self.assertRaises(InterpretationError) as ctx:
+            parse(TWO_ERRORS)
+        self.assertIn("line 1", str(ctx.exception))
+        self.assertNotIn("line 5", str(ctx.exception))
+
+    def test_collects_every_error(self):
+        syntax_tree.collect_parse_errors = True
+        with self.assertRaises(InterpretationError) as ctx:
+            parse(TWO_ERRORS)
+        message = str(ctx.exception)
+        self.assertIn("line 1", message)
+        self.assertIn("line 5", message)
+        self.assertEqual(
+            message.count("Scoped code statement must start with a keyword."), 2
+        )
+
+    def test_valid_code_parses_in_collect_mode(self):
+        syntax_tree.collect_parse_errors = True
+        self.assertEqual(len(parse("print(1)!\nprint(2)!\n")), 2)
+
+
+class TestSemicolonSeparator(unittest.TestCase):
+    def test_two_declarations_on_one_line(self):
+        statements = parse("const const x = 1; const const y = 2!\n")
+        self.assertEqual(len(statements), 2)
+        names = [
+            st.name.value
+            for possibilities in statements
+            for st in possibilities
+            if isinstance(st, VariableDeclaration)
+        ]
+        self.assertEqual(names, ["x", "y"])
+
+    def test_separated_statements_run(self):
+        output = run_code(
+            "const const x = 1; const const y = 2!\nprint(x)!\nprint(y)!\n"
+        )
+        self.assertEqual(output, "1\n2\n")
+
+    def test_spaced_semicolon_after_value_separates(self):
+        self.assertEqual(run_code("var x = 1 ; var y = 2!\nprint(y)!\n"), "2\n")
+
+    def test_leading_semicolon_is_still_not(self):
+        self.assertEqual(run_code("const z = ;true!\nprint(z)!\n"), "false\n")
+
+
+class TestSemicolonDisambiguation(unittest.TestCase):
+    def test_semicolon_in_operand_position_is_not(self):
+        self.assertEqual(run_code("const x = true!\nprint(;x)!\n"), "false\n")
+
+    def test_not_at_statement_start_after_terminated_statement(self):
+        statements = parse("x!\n;y!\n")
+        self.assertEqual(len(statements), 2)
+        self.assertEqual(statements[1][0].expression[0].type, TokenType.SEMICOLON)
+
+    def test_spaced_not_after_equals(self):
+        output = run_code("const ready = true!\nconst const x = ; ready!\nprint(x)!\n")
+        self.assertEqual(output, "false\n")
+
+    def test_spaced_not_after_expression_keyword(self):
+        statements = parse("if ; ready {\n   print(1)!\n}\n")
+        self.assertEqual(len(statements), 1)
+        output = run_code("const ready = true!\nif ; ready {\n   print(1)!\n}\n")
+        self.assertEqual(output, "")
+
+    def test_not_inside_list_literal(self):
+        output = run_code(
+            "const ready = false!\nconst x = [;ready, ready]!\nprint(x)!\n"
+        )
+        self.assertEqual(output, "[true, false]\n")
+
+    def test_not_after_operator(self):
+        output = run_code(
+            "const a = true!\nconst b = false!\nconst c = a & ;b!\nprint(c)!\n"
+        )
+        self.assertEqual(output, "true\n")
+
+
+class TestCheckSyntax(unittest.TestCase):
+    def test_valid_source(self):
+        self.assertIsNone(check_syntax("const const x = 1!\nprint(x)!\n"))
+
+    def test_invalid_source(self):
+        error = check_syntax('"x" {\n   print(1)!\n}\n')
+        self.assertIsNotNone(error)
+        self.assertIn("line 1", error)
+
+    def test_checks_every_file_section(self):
+        error = check_syntax('print(1)!\n===== second.gom =====\n"x" {\n}\n')
+        self.assertIsNotNone(error)
+        self.assertIn("second.gom", error)
+
+
+class TestArrowDeclaration(unittest.TestCase):
+    def test_arrow_declaration_parses(self):
+        (statement,) = [
+            st
+            for st in parse("const const x <- 5!\n")[0]
+            if isinstance(st, VariableDeclaration)
+        ]
+        self.assertEqual(statement.name.value, "x")
+        values = [t.value for t in statement.expression if t.value.strip()]
+        self.assertEqual(values, ["5"])
+
+    def test_arrow_declaration_runs(self):
+        output = run_code(
+            "const const x <- 5!\nconst y: Number <- 6!\nprint(x)!\nprint(y)!\n"
+        )
+        self.assertEqual(output, "5\n6\n")
+
+
+class TestConditionalBranches(unittest.TestCase):
+    def test_branches_belong_to_one_statement(self):
+        statements = parse(
+            "if x {\n   print(1)!\n} maybe {\n   print(2)!\n}"
+            " else {\n   print(3)!\n}\n"
+        )
+        self.assertEqual(len(statements), 1)
+        (conditional,) = statements[0]
+        self.assertIsInstance(conditional, Conditional)
+        self.assertEqual(len(conditional.code), 1)
+        self.assertEqual(len(conditional.maybe_code), 1)
+        self.assertEqual(len(conditional.else_code), 1)
+
+    def test_plain_if_has_no_branches(self):
+        statement = parse("if x {\n   print(1)!\n}\n")[0]
+        conditional = next(s for s in statement if isinstance(s, Conditional))
+        self.assertIsNone(conditional.maybe_code)
+        self.assertIsNone(conditional.else_code)
+
+    def test_branches_out_of_order(self):
+        with self.assertRaises(ParseError):
+            parse("if x {\n   print(1)!\n} else {\n   print(2)!\n} maybe {\n}\n")
+
+    def test_aliased_if_takes_branches(self):
+        output = run_code(
+            "const const zz = if!\n"
+            "zz false {\n   print(1)!\n} else {\n   print(2)!\n}\n"
+            "zz maybe {\n   fn f() => 3!\n} maybe {\n   print(4)!\n}\n"
+        )
+        self.assertEqual(output, "2\n4\n")
+
+    def test_only_if_blocks_take_branches(self):
+        for block in ("when x {\n}", "function f() => {\n}", "after x {\n}"):
+            with self.subTest(block=block):
+                tokens = tokenize("__test__", f"{block}\nelse {{\n}}\n")
+                statements = syntax_tree.split_into_statements(tokens)
+                self.assertEqual(len(statements), 2)
+
+
+class TestParseNextStatement(unittest.TestCase):
+    def test_matches_batch_parse(self):
+        code = (
+            "const const x: Number = 1; var var y = 2!\n"
+            "if x < y {\n"
+            "   print(x)!\n"
+            "}\n"
+            "\n"
+            "print(y)?\n"
+        )
+        tokens = tokenize("__test__", code)
+        statements, position = [], 0
+        while parsed := parse_next_statement("__test__", tokens, code, position):
+            statement, position = parsed
+            statements.append(statement)
+        self.assertEqual(statements, generate_syntax_tree("__test__", tokens, code))
+        self.assertEqual(len(statements), 4)
+
+    def test_empty_source(self):
+        tokens = tokenize("__test__", "\n   \n")
+        self.assertIsNone(parse_next_statement("__test__", tokens, "\n   \n", 0))
+
+
+if __name__ == "__main__":
+    unittest.main()